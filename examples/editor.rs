@@ -1,8 +1,4 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    time::Duration,
-};
+use std::{fs::File, io::BufReader, time::Duration};
 #[cfg(feature = "search")]
 use tuirealm::StateValue;
 use tuirealm::{
@@ -216,15 +212,10 @@ impl<'a> MockComponent for Editor<'a> {
 
 impl<'a> Default for Editor<'a> {
     fn default() -> Self {
-        let textarea = match fs::File::open("README.md") {
-            Ok(reader) => TextArea::new(
-                io::BufReader::new(reader)
-                    .lines()
-                    .map(|l| l.unwrap())
-                    .collect::<_>(),
-            ),
-            Err(_) => TextArea::default(),
-        };
+        let textarea = File::open("README.md")
+            .map(BufReader::new)
+            .and_then(TextArea::from_reader)
+            .unwrap_or_default();
         Self {
             component: textarea
                 .borders(
@@ -232,6 +223,7 @@ impl<'a> Default for Editor<'a> {
                         .color(Color::LightYellow)
                         .modifiers(BorderType::Double),
                 )
+                .cursor_gutter_style(Style::default().add_modifier(TextModifiers::BOLD))
                 .cursor_line_style(Style::default())
                 .cursor_style(Style::default().add_modifier(TextModifiers::REVERSED))
                 .footer_bar("Press <ESC> to quit", Style::default())
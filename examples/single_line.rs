@@ -153,7 +153,7 @@ impl<'a> MockComponent for Input<'a> {
 
 impl<'a> Default for Input<'a> {
     fn default() -> Self {
-        let textarea = TextArea::default().single_line(true);
+        let textarea = TextArea::default().single_line(true).enter_submits(true);
         Self {
             component: textarea
                 .borders(
@@ -263,10 +263,16 @@ impl<'a> Component<Msg, NoUserEvent> for Input<'a> {
             | Event::Keyboard(KeyEvent {
                 code: Key::Char('m'),
                 modifiers: KeyModifiers::CONTROL,
-            }) => {
-                self.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
-                Some(Msg::None)
-            }
+            }) => match self.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE)) {
+                CmdResult::Submit(state) => Some(Msg::Submit(
+                    state
+                        .unwrap_vec()
+                        .into_iter()
+                        .map(|x| x.unwrap_string())
+                        .collect(),
+                )),
+                _ => Some(Msg::None),
+            },
             Event::Keyboard(KeyEvent {
                 code: Key::Home, ..
             })
@@ -0,0 +1,102 @@
+//! # bracket
+//!
+//! Module which provides the helpers to find the matching bracket of the one at the cursor
+
+/// Pairs of opening and closing brackets
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// Get the position of the bracket at `cursor`, or of the one before it
+pub fn bracket_at(lines: &[String], (row, col): (usize, usize)) -> Option<(usize, usize)> {
+    let line = lines.get(row)?;
+    let is_bracket = |col: usize| {
+        line.chars().nth(col).is_some_and(|ch| {
+            BRACKETS
+                .iter()
+                .any(|(open, close)| ch == *open || ch == *close)
+        })
+    };
+    if is_bracket(col) {
+        Some((row, col))
+    } else if col > 0 && is_bracket(col - 1) {
+        Some((row, col - 1))
+    } else {
+        None
+    }
+}
+
+/// Get the position of the bracket matching the one at `pos`, skipping the nested pairs.
+/// Returns `None` if `pos` is not a bracket or if it's unmatched
+pub fn matching_bracket(lines: &[String], (row, col): (usize, usize)) -> Option<(usize, usize)> {
+    let ch = lines.get(row)?.chars().nth(col)?;
+    let (open, close, forward) = BRACKETS.iter().find_map(|(open, close)| match ch {
+        ch if ch == *open => Some((*open, *close, true)),
+        ch if ch == *close => Some((*open, *close, false)),
+        _ => None,
+    })?;
+    let mut depth = 0;
+    let mut visit = |(row, col): (usize, usize), ch: char| {
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth -= 1;
+        }
+        (depth == 0).then_some((row, col))
+    };
+    if forward {
+        lines.iter().enumerate().skip(row).find_map(|(r, line)| {
+            let skip = if r == row { col } else { 0 };
+            line.chars()
+                .enumerate()
+                .skip(skip)
+                .find_map(|(c, ch)| visit((r, c), ch))
+        })
+    } else {
+        lines[..=row]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(r, line)| {
+                let chars: Vec<char> = line.chars().collect();
+                let end = if r == row { col + 1 } else { chars.len() };
+                chars[..end]
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(c, ch)| visit((r, c), *ch))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_get_bracket_at_cursor() {
+        let lines = vec![String::from("f(x)")];
+        assert_eq!(bracket_at(&lines, (0, 1)), Some((0, 1)));
+        assert_eq!(bracket_at(&lines, (0, 2)), Some((0, 1)));
+        assert_eq!(bracket_at(&lines, (0, 4)), Some((0, 3)));
+        assert_eq!(bracket_at(&lines, (0, 0)), None);
+    }
+
+    #[test]
+    fn should_find_matching_bracket() {
+        let lines: Vec<String> = ["fn f(a: [u8; 2]) {", "    g(a)", "}", "(]"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(matching_bracket(&lines, (0, 4)), Some((0, 15)));
+        assert_eq!(matching_bracket(&lines, (0, 15)), Some((0, 4)));
+        assert_eq!(matching_bracket(&lines, (0, 8)), Some((0, 14)));
+        assert_eq!(matching_bracket(&lines, (0, 17)), Some((2, 0)));
+        assert_eq!(matching_bracket(&lines, (2, 0)), Some((0, 17)));
+        // unmatched brackets
+        assert_eq!(matching_bracket(&lines, (3, 0)), None);
+        assert_eq!(matching_bracket(&lines, (3, 1)), None);
+        assert_eq!(matching_bracket(&lines, (0, 0)), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! # comment
+//!
+//! Module which provides the helpers to comment and uncomment lines with a line comment prefix,
+//! or text with a block comment pair
+
+use super::indent::leading_whitespace;
+
+/// Comment `line` inserting `prefix` after its indentation, or uncomment it if it's already commented.
+/// A commented line may lack the trailing whitespace of `prefix`, such as an empty commented line
+pub fn toggle_comment(line: &str, prefix: &str) -> String {
+    let indent = leading_whitespace(line);
+    let text = &line[indent.len()..];
+    if let Some(text) = text.strip_prefix(prefix) {
+        format!("{indent}{text}")
+    } else if let Some(text) = text.strip_prefix(prefix.trim_end()) {
+        format!("{indent}{text}")
+    } else {
+        format!("{indent}{prefix}{text}")
+    }
+}
+
+/// Wrap `text` in the block comment `open` and `close`, or unwrap it if it's already wrapped.
+/// Wrapped text may lack the inner whitespace of `open` and `close`, such as `/*foo*/` for `/* ` and ` */`
+pub fn toggle_block_comment(text: &str, open: &str, close: &str) -> String {
+    let unwrapped = text
+        .strip_prefix(open)
+        .or_else(|| text.strip_prefix(open.trim_end()))
+        .and_then(|text| {
+            text.strip_suffix(close)
+                .or_else(|| text.strip_suffix(close.trim_start()))
+        });
+    match unwrapped {
+        Some(text) => text.to_string(),
+        None => format!("{open}{text}{close}"),
+    }
+}
+
+/// Toggle the block comment of `line` as `toggle_block_comment`, after its indentation
+pub fn toggle_block_comment_line(line: &str, open: &str, close: &str) -> String {
+    let indent = leading_whitespace(line);
+    let text = toggle_block_comment(&line[indent.len()..], open, close);
+    format!("{indent}{text}")
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_toggle_comment() {
+        assert_eq!(toggle_comment("  foo", "// "), "  // foo");
+        assert_eq!(toggle_comment("  // foo", "// "), "  foo");
+        assert_eq!(toggle_comment("\t//", "// "), "\t");
+        assert_eq!(toggle_comment("", "# "), "# ");
+        assert_eq!(toggle_comment("-- foo", "--"), " foo");
+    }
+
+    #[test]
+    fn should_toggle_block_comment() {
+        assert_eq!(toggle_block_comment("foo", "/* ", " */"), "/* foo */");
+        assert_eq!(toggle_block_comment("/* foo */", "/* ", " */"), "foo");
+        assert_eq!(toggle_block_comment("/*foo*/", "/* ", " */"), "foo");
+        assert_eq!(toggle_block_comment("/* a\nb */", "/* ", " */"), "a\nb");
+        assert_eq!(toggle_block_comment("/* foo", "/* ", " */"), "/* /* foo */");
+        assert_eq!(
+            toggle_block_comment_line("  <p>", "<!-- ", " -->"),
+            "  <!-- <p> -->"
+        );
+        assert_eq!(
+            toggle_block_comment_line("  <!-- <p> -->", "<!-- ", " -->"),
+            "  <p>"
+        );
+    }
+}
@@ -2,17 +2,54 @@
 //!
 //! Module which provides the Editor fmt, which is used to format the status lines of the textarea
 
+#[cfg(feature = "search")]
+use super::search::count_matches;
+use super::wrap::char_width;
 use super::TextAreaWidget;
 
 use lazy_regex::{Lazy, Regex};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::SystemTime;
 use tuirealm::props::Style;
 
 /// FmtCallback: LineFmt, widget, wrkstr, prepend
 type FmtCallback = fn(&LineFmt, &TextAreaWidget, &str, &str) -> String;
 
+/// A callback formatting a custom key of the status and footer format.
+///
+/// Unlike the builtin keys, which are plain `fn` pointers, custom keys are boxed closures, so they
+/// may capture their state at the cost of a reference count and of a dynamic call; since they are
+/// called on every render, they should be cheap, returning a value computed elsewhere rather than
+/// querying it.
+pub type CustomFmtCallback = Arc<dyn Fn(&TextAreaWidget) -> String>;
+
+/// A callback formatting the current time for the `{TIME}` key, such as the local time with the
+/// date and time crate used by the application
+pub type TimeFmtCallback = Arc<dyn Fn(SystemTime) -> String>;
+
+/// The function formatting a key
+#[derive(Clone)]
+enum KeyFmt {
+    Builtin(FmtCallback),
+    Custom(CustomFmtCallback),
+}
+
 // Keys
+const FMT_KEY_CHARS: &str = "CHARS";
+const FMT_KEY_FILENAME: &str = "FILENAME";
+const FMT_KEY_INDENT_MODE: &str = "INDENT_MODE";
 const FMT_KEY_ROW: &str = "ROW";
+const FMT_KEY_TAB_SIZE: &str = "TABSIZE";
+#[cfg(feature = "search")]
+const FMT_KEY_SEARCH: &str = "SEARCH";
 const FMT_KEY_COLUMN: &str = "COL";
+const FMT_KEY_COLUMN_BYTES: &str = "COL_BYTES";
+const FMT_KEY_LINES: &str = "LINES";
+const FMT_KEY_MODIFIED: &str = "MODIFIED";
+const FMT_KEY_PERCENT: &str = "PERCENT";
+const FMT_KEY_TIME: &str = "TIME";
+const FMT_KEY_WORDS: &str = "WORDS";
 
 /**
  * Regex matches:
@@ -23,14 +60,27 @@ static FMT_KEY_REGEX: Lazy<Regex> = lazy_regex!(r"\{(.*?)\}");
 pub struct LineFmt {
     call_chain: CallChainBlock,
     style: Style,
+    /// Name of the edited file, displayed by the `{FILENAME}` key
+    filename: Option<String>,
+    /// Marker displayed by the `{MODIFIED}` key, set while the text is modified
+    modified: Option<String>,
+    /// Callback formatting the time displayed by the `{TIME}` key
+    time_fmt: Option<TimeFmtCallback>,
 }
 
 impl LineFmt {
-    /// Instantiates a new `LineFmt`
-    pub fn new(fmt_str: &str, style: Style) -> Self {
+    /// Instantiates a new `LineFmt`, formatting the keys which are not builtin with `custom_keys`
+    pub fn new(
+        fmt_str: &str,
+        style: Style,
+        custom_keys: &HashMap<String, CustomFmtCallback>,
+    ) -> Self {
         LineFmt {
-            call_chain: Self::make_callchain(fmt_str),
+            call_chain: Self::make_callchain(fmt_str, custom_keys),
             style,
+            filename: None,
+            modified: None,
+            time_fmt: None,
         }
     }
 
@@ -45,20 +95,139 @@ impl LineFmt {
         self.style
     }
 
+    /// Set the name of the edited file displayed by the `{FILENAME}` key
+    pub fn set_filename(&mut self, filename: Option<String>) {
+        self.filename = filename;
+    }
+
+    /// Set the marker displayed by the `{MODIFIED}` key; `None` if the text is not modified
+    pub fn set_modified(&mut self, marker: Option<String>) {
+        self.modified = marker;
+    }
+
+    /// Set the callback formatting the time displayed by the `{TIME}` key; `None` to display nothing
+    pub fn set_time_fmt(&mut self, time_fmt: Option<TimeFmtCallback>) {
+        self.time_fmt = time_fmt;
+    }
+
+    fn fmt_chars(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let chars: usize = widget.lines().iter().map(|x| x.chars().count()).sum();
+        format!("{}{}{}", wrkstr, prepend, chars)
+    }
+
+    /// Format the display column of the cursor, counting wide chars as two columns, combining chars
+    /// as none and tabs up to the next tab stop
     fn fmt_col(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
-        format!("{}{}{}", wrkstr, prepend, widget.cursor().1 + 1)
+        let (row, col) = widget.cursor();
+        let tab_len = widget.tab_length();
+        let display_col = widget.lines()[row]
+            .chars()
+            .take(col)
+            .fold(0, |x, ch| x + char_width(ch, x, tab_len));
+        format!("{}{}{}", wrkstr, prepend, display_col + 1)
+    }
+
+    /// Format the byte offset of the cursor in its line, starting from 1
+    fn fmt_col_bytes(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let (row, col) = widget.cursor();
+        let offset: usize = widget.lines()[row]
+            .chars()
+            .take(col)
+            .map(char::len_utf8)
+            .sum();
+        format!("{}{}{}", wrkstr, prepend, offset + 1)
+    }
+
+    /// Format the file name, which is `[No Name]` if unset or empty
+    fn fmt_filename(&self, _: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let filename = self
+            .filename
+            .as_deref()
+            .filter(|x| !x.is_empty())
+            .unwrap_or("[No Name]");
+        format!("{}{}{}", wrkstr, prepend, filename)
+    }
+
+    /// Format whether indenting inserts tabs or spaces
+    fn fmt_indent_mode(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let mode = match widget.hard_tab_indent() {
+            true => "Tab",
+            false => "Spaces",
+        };
+        format!("{}{}{}", wrkstr, prepend, mode)
+    }
+
+    fn fmt_lines(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        format!("{}{}{}", wrkstr, prepend, widget.lines().len())
+    }
+
+    fn fmt_modified(&self, _: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        format!(
+            "{}{}{}",
+            wrkstr,
+            prepend,
+            self.modified.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Format how far the cursor row is through the text.
+    /// A text made of a single line is always at 100%
+    fn fmt_percent(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let last_row = widget.lines().len().saturating_sub(1);
+        let percent = match last_row {
+            0 => 100,
+            _ => (widget.cursor().0 * 100) / last_row,
+        };
+        format!("{}{}{}%", wrkstr, prepend, percent)
     }
 
     fn fmt_row(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
         format!("{}{}{}", wrkstr, prepend, widget.cursor().0 + 1)
     }
 
+    fn fmt_tab_size(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        format!("{}{}{}", wrkstr, prepend, widget.tab_length())
+    }
+
+    #[cfg(feature = "search")]
+    fn fmt_search(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        match widget.search_pattern() {
+            Some(pattern) => {
+                let (current, total) = count_matches(pattern, widget.lines(), widget.cursor());
+                format!("{}{}[{}/{}]", wrkstr, prepend, current, total)
+            }
+            None => format!("{}{}", wrkstr, prepend),
+        }
+    }
+
+    /// Format the current time with the time callback, or nothing if unset
+    fn fmt_time(&self, _: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let time = self
+            .time_fmt
+            .as_ref()
+            .map(|time_fmt| time_fmt(SystemTime::now()))
+            .unwrap_or_default();
+        format!("{}{}{}", wrkstr, prepend, time)
+    }
+
+    fn fmt_words(&self, widget: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
+        let words: usize = widget
+            .lines()
+            .iter()
+            .map(|x| x.split_whitespace().count())
+            .sum();
+        format!("{}{}{}", wrkstr, prepend, words)
+    }
+
     fn fmt_none(&self, _: &TextAreaWidget, wrkstr: &str, prepend: &str) -> String {
         format!("{}{}", wrkstr, prepend)
     }
 
     /// Make a callchain starting from the fmt str
-    fn make_callchain(fmt_str: &str) -> CallChainBlock {
+    fn make_callchain(
+        fmt_str: &str,
+        custom_keys: &HashMap<String, CustomFmtCallback>,
+    ) -> CallChainBlock {
         // Init chain block
         let mut callchain: Option<CallChainBlock> = None;
         // Track index of the last match found, to get the prefix for each token
@@ -73,9 +242,25 @@ impl LineFmt {
             last_index += prepend.len() + regex_match[0].len();
             // Match attributes
             let callback = match regex_match.get(1).map(|x| x.as_str()) {
-                Some(FMT_KEY_COLUMN) => Self::fmt_col,
-                Some(FMT_KEY_ROW) => Self::fmt_row,
-                Some(_) | None => Self::fmt_none,
+                Some(FMT_KEY_CHARS) => KeyFmt::Builtin(Self::fmt_chars),
+                Some(FMT_KEY_COLUMN) => KeyFmt::Builtin(Self::fmt_col),
+                Some(FMT_KEY_COLUMN_BYTES) => KeyFmt::Builtin(Self::fmt_col_bytes),
+                Some(FMT_KEY_FILENAME) => KeyFmt::Builtin(Self::fmt_filename),
+                Some(FMT_KEY_INDENT_MODE) => KeyFmt::Builtin(Self::fmt_indent_mode),
+                Some(FMT_KEY_LINES) => KeyFmt::Builtin(Self::fmt_lines),
+                Some(FMT_KEY_MODIFIED) => KeyFmt::Builtin(Self::fmt_modified),
+                Some(FMT_KEY_PERCENT) => KeyFmt::Builtin(Self::fmt_percent),
+                Some(FMT_KEY_ROW) => KeyFmt::Builtin(Self::fmt_row),
+                #[cfg(feature = "search")]
+                Some(FMT_KEY_SEARCH) => KeyFmt::Builtin(Self::fmt_search),
+                Some(FMT_KEY_TAB_SIZE) => KeyFmt::Builtin(Self::fmt_tab_size),
+                Some(FMT_KEY_TIME) => KeyFmt::Builtin(Self::fmt_time),
+                Some(FMT_KEY_WORDS) => KeyFmt::Builtin(Self::fmt_words),
+                Some(key) => match custom_keys.get(key) {
+                    Some(custom) => KeyFmt::Custom(custom.clone()),
+                    None => KeyFmt::Builtin(Self::fmt_none),
+                },
+                None => KeyFmt::Builtin(Self::fmt_none),
             };
             // Create a callchain or push new element to its back
             match callchain.as_mut() {
@@ -84,15 +269,21 @@ impl LineFmt {
             }
         }
         // Push remaining str
-        if last_index + 1 != fmt_str.len() {
+        if last_index < fmt_str.len() {
             let prepend = String::from(&fmt_str[last_index..]);
             match callchain.as_mut() {
-                None => callchain = Some(CallChainBlock::new(Self::fmt_none, prepend)),
-                Some(chain_block) => chain_block.push(Self::fmt_none, prepend),
+                None => {
+                    callchain = Some(CallChainBlock::new(
+                        KeyFmt::Builtin(Self::fmt_none),
+                        prepend,
+                    ))
+                }
+                Some(chain_block) => chain_block.push(KeyFmt::Builtin(Self::fmt_none), prepend),
             }
         }
         // Finalize and return
-        callchain.unwrap_or_else(|| CallChainBlock::new(Self::fmt_none, String::new()))
+        callchain
+            .unwrap_or_else(|| CallChainBlock::new(KeyFmt::Builtin(Self::fmt_none), String::new()))
     }
 }
 
@@ -102,7 +293,7 @@ impl LineFmt {
 /// This method provides an extremely fast way to format fs entries
 struct CallChainBlock {
     /// The function to call to format current item
-    func: FmtCallback,
+    func: KeyFmt,
     /// All the content which is between two `{KEY}` items
     prepend: String,
     /// The next block to format
@@ -111,7 +302,7 @@ struct CallChainBlock {
 
 impl CallChainBlock {
     /// Create a new `CallChainBlock`
-    pub fn new(func: FmtCallback, prepend: String) -> Self {
+    pub fn new(func: KeyFmt, prepend: String) -> Self {
         CallChainBlock {
             func,
             prepend,
@@ -122,7 +313,10 @@ impl CallChainBlock {
     /// Call next callback in the CallChain
     pub fn fmt(&self, fmt: &LineFmt, widget: &TextAreaWidget, wrkstr: &str) -> String {
         // Call func
-        let new_str: String = (self.func)(fmt, widget, wrkstr, self.prepend.as_str());
+        let new_str: String = match &self.func {
+            KeyFmt::Builtin(func) => func(fmt, widget, wrkstr, self.prepend.as_str()),
+            KeyFmt::Custom(func) => format!("{}{}{}", wrkstr, self.prepend, func(widget)),
+        };
         // If next is some, call next fmt, otherwise (END OF CHAIN) return new_str
         match &self.next_block {
             Some(block) => block.fmt(fmt, widget, new_str.as_str()),
@@ -131,7 +325,7 @@ impl CallChainBlock {
     }
 
     /// Push func to the last element in the Call chain
-    pub fn push(&mut self, func: FmtCallback, prepend: String) {
+    pub fn push(&mut self, func: KeyFmt, prepend: String) {
         // Call recursively until an element with next_block equal to None is found
         match &mut self.next_block {
             None => self.next_block = Some(Box::new(CallChainBlock::new(func, prepend))),
@@ -147,37 +341,170 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn should_fmt_chars() {
+        let widget = get_widget();
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        assert_eq!(
+            fmt.fmt_chars(&widget, "Words 2", " Chars ").as_str(),
+            "Words 2 Chars 11"
+        );
+    }
+
     #[test]
     fn should_fmt_column() {
         let widget = get_widget();
-        let fmt = LineFmt::new("", Style::default());
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
         assert_eq!(
             fmt.fmt_col(&widget, "Row 4", " Col ").as_str(),
             "Row 4 Col 1"
         );
+        let mut widget = TextAreaWidget::new(vec![String::from("日本e\u{301}\tx")]);
+        widget.move_cursor(tui_textarea::CursorMove::End);
+        widget.move_cursor(tui_textarea::CursorMove::Back);
+        let fmt = LineFmt::new("{COL} {COL_BYTES}", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "9 11");
+        widget.move_cursor(tui_textarea::CursorMove::Back);
+        assert_eq!(fmt.fmt(&widget).as_str(), "6 10");
+    }
+
+    #[test]
+    fn should_fmt_filename() {
+        let widget = get_widget();
+        let mut fmt = LineFmt::new("{FILENAME} {ROW}", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "[No Name] 1");
+        fmt.set_filename(Some(String::new()));
+        assert_eq!(fmt.fmt(&widget).as_str(), "[No Name] 1");
+        fmt.set_filename(Some(String::from("README.md")));
+        assert_eq!(fmt.fmt(&widget).as_str(), "README.md 1");
+    }
+
+    #[test]
+    fn should_fmt_lines() {
+        let widget = get_widget();
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        assert_eq!(
+            fmt.fmt_lines(&widget, "Row 1", " of ").as_str(),
+            "Row 1 of 2"
+        );
+    }
+
+    #[test]
+    fn should_fmt_modified() {
+        let widget = get_widget();
+        let mut fmt = LineFmt::new("README.md{MODIFIED}", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "README.md");
+        fmt.set_modified(Some(String::from(" [+]")));
+        assert_eq!(fmt.fmt(&widget).as_str(), "README.md [+]");
+    }
+
+    #[test]
+    fn should_fmt_percent() {
+        let mut widget = TextAreaWidget::new((0..5).map(|x| x.to_string()).collect());
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt_percent(&widget, "Ln 1", " ").as_str(), "Ln 1 0%");
+        widget.move_cursor(tui_textarea::CursorMove::Down);
+        assert_eq!(fmt.fmt_percent(&widget, "", "").as_str(), "25%");
+        widget.move_cursor(tui_textarea::CursorMove::Bottom);
+        assert_eq!(fmt.fmt_percent(&widget, "", "").as_str(), "100%");
+        let widget = TextAreaWidget::new(vec![String::from("hello")]);
+        assert_eq!(fmt.fmt_percent(&widget, "", "").as_str(), "100%");
     }
 
     #[test]
     fn should_fmt_row() {
         let widget = get_widget();
-        let fmt = LineFmt::new("", Style::default());
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
         assert_eq!(
             fmt.fmt_row(&widget, "Col 5", " Row ").as_str(),
             "Col 5 Row 1"
         );
     }
 
+    #[test]
+    fn should_fmt_indentation() {
+        let mut widget = get_widget();
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        widget.set_tab_length(2);
+        assert_eq!(fmt.fmt_tab_size(&widget, "", "Size: ").as_str(), "Size: 2");
+        assert_eq!(fmt.fmt_indent_mode(&widget, "", "").as_str(), "Spaces");
+        widget.set_hard_tab_indent(true);
+        assert_eq!(fmt.fmt_indent_mode(&widget, "", "").as_str(), "Tab");
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_fmt_search() {
+        let mut widget = get_widget();
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt_search(&widget, "Ln 1", " ").as_str(), "Ln 1 ");
+        widget.set_search_pattern("o").unwrap();
+        assert_eq!(fmt.fmt_search(&widget, "", "").as_str(), "[0/2]");
+        widget.search_forward(true);
+        assert_eq!(fmt.fmt_search(&widget, "", "").as_str(), "[1/2]");
+        widget.set_search_pattern("x").unwrap();
+        assert_eq!(fmt.fmt_search(&widget, "", "").as_str(), "[0/0]");
+    }
+
+    #[test]
+    fn should_fmt_time() {
+        let widget = get_widget();
+        let mut fmt = LineFmt::new("[{TIME}]", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "[]");
+        fmt.set_time_fmt(Some(Arc::new(|_| String::from("12:00"))));
+        assert_eq!(fmt.fmt(&widget).as_str(), "[12:00]");
+    }
+
+    #[test]
+    fn should_fmt_words() {
+        let widget = TextAreaWidget::new(vec![
+            String::from("hello  wörld"),
+            String::new(),
+            String::from("\tfoo bar "),
+        ]);
+        let fmt = LineFmt::new("", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt_words(&widget, "", "Words ").as_str(), "Words 4");
+        assert_eq!(fmt.fmt_chars(&widget, "", "Chars ").as_str(), "Chars 21");
+    }
+
     #[test]
     fn should_fmt_with_keys() {
         let widget = get_widget();
-        let fmt = LineFmt::new("Row {ROW} Col {COL} | README.md", Style::default());
+        let fmt = LineFmt::new(
+            "Row {ROW} Col {COL} | README.md",
+            Style::default(),
+            &HashMap::new(),
+        );
         assert_eq!(fmt.fmt(&widget).as_str(), "Row 1 Col 1 | README.md");
     }
 
+    #[test]
+    fn should_fmt_with_key_at_the_end() {
+        let widget = get_widget();
+        let fmt = LineFmt::new("{ROW}/{LINES}", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "1/2");
+        let fmt = LineFmt::new("{ROW}/{LINES}!", Style::default(), &HashMap::new());
+        assert_eq!(fmt.fmt(&widget).as_str(), "1/2!");
+    }
+
+    #[test]
+    fn should_fmt_with_custom_keys() {
+        let widget = get_widget();
+        let mut keys: HashMap<String, CustomFmtCallback> = HashMap::new();
+        keys.insert(String::from("MODE"), Arc::new(|_| String::from("INSERT")));
+        keys.insert(String::from("ROW"), Arc::new(|_| String::from("custom")));
+        keys.insert(
+            String::from("FIRST"),
+            Arc::new(|widget| widget.lines()[0].clone()),
+        );
+        let fmt = LineFmt::new("{MODE} {FIRST} {ROW} {BRANCH}", Style::default(), &keys);
+        assert_eq!(fmt.fmt(&widget).as_str(), "INSERT hello 1 ");
+    }
+
     #[test]
     fn should_fmt_with_no_key() {
         let widget = get_widget();
-        let fmt = LineFmt::new("Press <ESC> to quit", Style::default());
+        let fmt = LineFmt::new("Press <ESC> to quit", Style::default(), &HashMap::new());
         assert_eq!(fmt.fmt(&widget).as_str(), "Press <ESC> to quit");
     }
 
@@ -0,0 +1,61 @@
+//! # focus_cursor
+//!
+//! Module which provides where the cursor is moved when the textarea gains focus
+
+use std::str::FromStr;
+
+/// Where the cursor is moved when the textarea gains focus
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FocusCursorBehavior {
+    /// Keep the cursor where it was when the focus was lost
+    #[default]
+    Keep,
+    /// Move the cursor to the end of the text
+    ToEnd,
+    /// Move the cursor to the start of the text
+    ToStart,
+}
+
+impl FocusCursorBehavior {
+    /// Get the name of the behavior, which is set as `TEXTAREA_FOCUS_CURSOR_BEHAVIOR`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::ToEnd => "to-end",
+            Self::ToStart => "to-start",
+        }
+    }
+}
+
+impl FromStr for FocusCursorBehavior {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep" => Ok(Self::Keep),
+            "to-end" => Ok(Self::ToEnd),
+            "to-start" => Ok(Self::ToStart),
+            _ => Err(format!("unknown focus cursor behavior: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_focus_cursor_behavior() {
+        for behavior in [
+            FocusCursorBehavior::Keep,
+            FocusCursorBehavior::ToEnd,
+            FocusCursorBehavior::ToStart,
+        ] {
+            assert_eq!(behavior.as_str().parse(), Ok(behavior));
+        }
+        assert!("end".parse::<FocusCursorBehavior>().is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! # history
+//!
+//! Module which provides the edit history of the textarea, used to undo and redo changes.
+//!
+//! It replaces the history of the widget, which records each of the widget operations making up a
+//! command as its own step: here a command is undone at once, and the edits rejected for exceeding
+//! the limits are reverted without being recorded
+
+use std::collections::VecDeque;
+
+/// A change to the text, described as the lines replaced starting from `row`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// Index of the first replaced line
+    pub row: usize,
+    /// Replaced lines before the edit
+    pub before: Vec<String>,
+    /// Replaced lines after the edit
+    pub after: Vec<String>,
+    /// Cursor position before the edit
+    pub cursor_before: (usize, usize),
+    /// Cursor position after the edit
+    pub cursor_after: (usize, usize),
+}
+
+impl Edit {
+    /// Make the edit which turns `before` into `after`, keeping only the lines which changed.
+    /// Returns `None` if the lines are the same
+    pub fn diff(
+        before: &[String],
+        after: &[String],
+        cursor_before: (usize, usize),
+        cursor_after: (usize, usize),
+    ) -> Option<Self> {
+        let prefix = before
+            .iter()
+            .zip(after.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        if prefix == before.len() && prefix == after.len() {
+            return None;
+        }
+        let suffix = before
+            .iter()
+            .rev()
+            .zip(after.iter().rev())
+            .take(before.len().min(after.len()) - prefix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        Some(Self {
+            row: prefix,
+            before: before[prefix..before.len() - suffix].to_vec(),
+            after: after[prefix..after.len() - suffix].to_vec(),
+            cursor_before,
+            cursor_after,
+        })
+    }
+}
+
+/// The list of edits applied to the text.
+/// Each edit is recorded along with the revision of the text it makes, so that the text can be
+/// told to be changed without comparing it
+#[derive(Debug, Clone)]
+pub struct History {
+    edits: VecDeque<(u64, Edit)>,
+    /// Amount of edits currently applied
+    index: usize,
+    max_items: usize,
+    /// Whether the last edit was recorded by `push_typing`, so that it can be continued
+    typing: bool,
+    /// Revision of the text when no edit is applied
+    base: u64,
+    /// Last assigned revision
+    last: u64,
+}
+
+impl History {
+    /// Instantiates a new `History` which remembers up to `max_items` edits
+    pub fn new(max_items: usize) -> Self {
+        Self {
+            edits: VecDeque::new(),
+            index: 0,
+            max_items,
+            typing: false,
+            base: 0,
+            last: 0,
+        }
+    }
+
+    /// Get the revision of the text, which changes on every recorded edit and is restored by
+    /// undoing and redoing
+    pub fn revision(&self) -> u64 {
+        match self.index {
+            0 => self.base,
+            index => self.edits[index - 1].0,
+        }
+    }
+
+    /// Set the amount of remembered edits, dropping the oldest ones in excess
+    pub fn set_max_items(&mut self, max_items: usize) {
+        self.max_items = max_items;
+        while self.edits.len() > max_items {
+            self.pop_front();
+        }
+    }
+
+    /// Forget all the recorded edits, starting a new revision
+    pub fn clear(&mut self) {
+        self.edits.clear();
+        self.index = 0;
+        self.typing = false;
+        self.base = self.next_revision();
+    }
+
+    /// Record a new edit, dropping all the undone edits
+    pub fn push(&mut self, edit: Edit) {
+        let revision = self.next_revision();
+        if self.max_items == 0 {
+            self.base = revision;
+            return;
+        }
+        self.edits.truncate(self.index);
+        if self.edits.len() == self.max_items {
+            self.pop_front();
+        }
+        self.edits.push_back((revision, edit));
+        self.index = self.edits.len();
+        self.typing = false;
+    }
+
+    /// Record an edit made by typing, merging it into the last edit if that was recorded by
+    /// `push_typing` too and the new edit continues it from where it left the cursor
+    pub fn push_typing(&mut self, edit: Edit) {
+        let continued = self.typing && self.index == self.edits.len();
+        let revision = self.last + 1;
+        if let Some((last_revision, last)) = self.edits.back_mut().filter(|_| continued) {
+            if last.row == edit.row
+                && last.after == edit.before
+                && last.cursor_after == edit.cursor_before
+            {
+                last.after = edit.after;
+                last.cursor_after = edit.cursor_after;
+                *last_revision = revision;
+                self.last = revision;
+                return;
+            }
+        }
+        self.push(edit);
+        self.typing = self.max_items > 0;
+    }
+
+    /// Make the next edit recorded by `push_typing` a new step
+    pub fn break_typing(&mut self) {
+        self.typing = false;
+    }
+
+    /// Whether there is an edit to revert
+    pub fn can_undo(&self) -> bool {
+        self.index > 0
+    }
+
+    /// Whether there is an undone edit to apply again
+    pub fn can_redo(&self) -> bool {
+        self.index < self.edits.len()
+    }
+
+    /// Step back in history, returning the edit to revert
+    pub fn undo(&mut self) -> Option<Edit> {
+        self.typing = false;
+        self.index = self.index.checked_sub(1)?;
+        self.edits.get(self.index).map(|(_, edit)| edit.clone())
+    }
+
+    /// Step forward in history, returning the edit to apply again
+    pub fn redo(&mut self) -> Option<Edit> {
+        self.typing = false;
+        let (_, edit) = self.edits.get(self.index).cloned()?;
+        self.index += 1;
+        Some(edit)
+    }
+
+    fn next_revision(&mut self) -> u64 {
+        self.last += 1;
+        self.last
+    }
+
+    /// Drop the oldest edit; if it is applied, the text it makes becomes the base revision
+    fn pop_front(&mut self) {
+        if let Some((revision, _)) = self.edits.pop_front() {
+            if self.index > 0 {
+                self.base = revision;
+                self.index -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_diff_lines() {
+        let edit = Edit::diff(
+            &lines(&["a", "b", "c", "d"]),
+            &lines(&["a", "x", "y", "d"]),
+            (1, 0),
+            (2, 1),
+        )
+        .unwrap();
+        assert_eq!(edit.row, 1);
+        assert_eq!(edit.before, lines(&["b", "c"]));
+        assert_eq!(edit.after, lines(&["x", "y"]));
+        // inserted lines
+        let edit = Edit::diff(
+            &lines(&["a", "a"]),
+            &lines(&["a", "a", "a"]),
+            (0, 0),
+            (1, 0),
+        )
+        .unwrap();
+        assert_eq!(edit.row, 2);
+        assert!(edit.before.is_empty());
+        assert_eq!(edit.after, lines(&["a"]));
+        // same lines
+        assert!(Edit::diff(&lines(&["a"]), &lines(&["a"]), (0, 0), (0, 0)).is_none());
+    }
+
+    #[test]
+    fn should_undo_and_redo() {
+        let mut history = History::new(2);
+        assert!(history.undo().is_none());
+        history.push(edit("a"));
+        history.clear();
+        assert!(history.undo().is_none());
+        history.push(edit("a"));
+        history.push(edit("b"));
+        history.push(edit("c"));
+        assert_eq!(history.undo().unwrap().after, lines(&["c"]));
+        assert_eq!(history.undo().unwrap().after, lines(&["b"]));
+        assert!(history.undo().is_none());
+        assert_eq!(history.redo().unwrap().after, lines(&["b"]));
+        history.push(edit("d"));
+        assert!(history.redo().is_none());
+        assert_eq!(history.undo().unwrap().after, lines(&["d"]));
+    }
+
+    #[test]
+    fn should_merge_typing_edits() {
+        let mut history = History::new(8);
+        let typing = |before: &str, after: &str, col: usize| Edit {
+            row: 0,
+            before: lines(&[before]),
+            after: lines(&[after]),
+            cursor_before: (0, col),
+            cursor_after: (0, col + 1),
+        };
+        history.push_typing(typing("", "a", 0));
+        history.push_typing(typing("a", "ab", 1));
+        // the cursor moved
+        history.push_typing(typing("ab", "xab", 0));
+        history.break_typing();
+        history.push_typing(typing("xab", "xyab", 1));
+        history.push(edit("c"));
+        history.push_typing(typing("c", "cd", 1));
+        assert_eq!(history.undo().unwrap().after, lines(&["cd"]));
+        assert_eq!(history.undo().unwrap().after, lines(&["c"]));
+        assert_eq!(history.undo().unwrap().after, lines(&["xyab"]));
+        assert_eq!(history.undo().unwrap().after, lines(&["xab"]));
+        let edit = history.undo().unwrap();
+        assert_eq!(edit.before, lines(&[""]));
+        assert_eq!(edit.after, lines(&["ab"]));
+        assert_eq!(edit.cursor_before, (0, 0));
+        assert_eq!(edit.cursor_after, (0, 2));
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn should_not_record_without_max_items() {
+        let mut history = History::new(0);
+        history.push(edit("a"));
+        assert!(!history.can_undo());
+        assert!(history.undo().is_none());
+        let mut history = History::new(4);
+        history.push(edit("a"));
+        history.push(edit("b"));
+        history.set_max_items(1);
+        assert_eq!(history.undo().unwrap().after, lines(&["b"]));
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn should_track_revision() {
+        let mut history = History::new(2);
+        let initial = history.revision();
+        history.push(edit("a"));
+        let a = history.revision();
+        assert_ne!(a, initial);
+        history.push(edit("b"));
+        history.undo();
+        assert_eq!(history.revision(), a);
+        history.undo();
+        assert_eq!(history.revision(), initial);
+        history.redo();
+        history.redo();
+        let b = history.revision();
+        // the oldest edit is dropped, but its revision is kept
+        history.push(edit("c"));
+        history.undo();
+        assert_eq!(history.revision(), b);
+        history.undo();
+        assert_eq!(history.revision(), a);
+        // typing continues the edit, but changes the text
+        history.push_typing(edit("d"));
+        let d = history.revision();
+        history.push_typing(Edit {
+            row: 0,
+            before: lines(&["d"]),
+            after: lines(&["de"]),
+            cursor_before: (0, 0),
+            cursor_after: (0, 0),
+        });
+        assert_ne!(history.revision(), d);
+        history.undo();
+        assert_eq!(history.revision(), a);
+        // edits change the revision even if they aren't remembered
+        let mut history = History::new(0);
+        history.push(edit("a"));
+        assert_ne!(history.revision(), initial);
+    }
+
+    fn edit(line: &str) -> Edit {
+        Edit {
+            row: 0,
+            before: Vec::new(),
+            after: lines(&[line]),
+            cursor_before: (0, 0),
+            cursor_after: (0, 0),
+        }
+    }
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|x| x.to_string()).collect()
+    }
+}
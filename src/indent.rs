@@ -0,0 +1,190 @@
+//! # indent
+//!
+//! Module which provides the helpers to measure and rewrite the leading whitespace of the lines
+
+use super::wrap::char_width;
+
+/// Get the leading whitespace of `line`
+pub fn leading_whitespace(line: &str) -> &str {
+    let end = line
+        .find(|c: char| c != ' ' && c != '\t')
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+/// Get the display width of the leading whitespace of `line`
+pub fn indent_width(line: &str, tab_len: u8) -> usize {
+    leading_whitespace(line)
+        .chars()
+        .fold(0, |col, ch| col + char_width(ch, col, tab_len))
+}
+
+/// Make the whitespace which indents a line by `width` columns.
+/// With `hard_tab` the indentation is made of tabs, padded with spaces to reach `width`
+pub fn make_indent(width: usize, tab_len: u8, hard_tab: bool) -> String {
+    if hard_tab && tab_len > 0 {
+        let tabs = width / tab_len as usize;
+        let spaces = width % tab_len as usize;
+        format!("{}{}", "\t".repeat(tabs), " ".repeat(spaces))
+    } else {
+        " ".repeat(width)
+    }
+}
+
+/// Rewrite the leading whitespace of `line` with tabs or spaces, keeping its width
+pub fn retab(line: &str, tab_len: u8, hard_tab: bool) -> String {
+    let indent = leading_whitespace(line);
+    format!(
+        "{}{}",
+        make_indent(indent_width(line, tab_len), tab_len, hard_tab),
+        &line[indent.len()..]
+    )
+}
+
+/// Replace the tabs in `line` with the spaces up to the next tab stop
+pub fn expand_tabs(line: &str, tab_len: u8) -> String {
+    let mut col = 0;
+    let mut expanded = String::with_capacity(line.len());
+    for ch in line.chars() {
+        let width = char_width(ch, col, tab_len);
+        match ch {
+            '\t' => expanded.push_str(&" ".repeat(width)),
+            ch => expanded.push(ch),
+        }
+        col += width;
+    }
+    expanded
+}
+
+/// Remove up to one tab stop of leading whitespace from `line`
+pub fn dedent(line: &str, tab_len: u8) -> &str {
+    let mut width = 0;
+    let mut end = 0;
+    for ch in leading_whitespace(line).chars() {
+        if width >= tab_len as usize {
+            break;
+        }
+        width += char_width(ch, width, tab_len);
+        end += ch.len_utf8();
+    }
+    &line[end..]
+}
+
+/// Get the column of the cursor at `col` in `before`, once the head of `before` has been
+/// rewritten into `after`, such as its indentation.
+/// A column inside the indentation is clamped to the new indentation length,
+/// while the text after it keeps its position relative to the end of the line
+pub fn reindent_col(before: &str, after: &str, col: usize) -> usize {
+    let old = leading_whitespace(before).chars().count();
+    let new = leading_whitespace(after).chars().count();
+    if col >= old {
+        (col + after.chars().count())
+            .saturating_sub(before.chars().count())
+            .max(new)
+    } else {
+        col.min(new)
+    }
+}
+
+/// Get the nearest row before `row` indented less than `row`: its parent in an indent-based structure.
+/// Blank lines are skipped
+pub fn parent_row(lines: &[String], row: usize, tab_len: u8) -> Option<usize> {
+    let width = indent_width(lines.get(row)?, tab_len);
+    (0..row)
+        .rev()
+        .find(|&i| !is_blank(&lines[i]) && indent_width(&lines[i], tab_len) < width)
+}
+
+/// Get the next row after `row` indented as `row`, without leaving the block `row` belongs to.
+/// Blank lines and the lines indented more than `row` are skipped
+pub fn next_sibling_row(lines: &[String], row: usize, tab_len: u8) -> Option<usize> {
+    let width = indent_width(lines.get(row)?, tab_len);
+    lines
+        .iter()
+        .enumerate()
+        .skip(row + 1)
+        .filter(|(_, line)| !is_blank(line))
+        .map(|(i, line)| (i, indent_width(line, tab_len)))
+        .find(|&(_, w)| w <= width)
+        .filter(|&(_, w)| w == width)
+        .map(|(i, _)| i)
+}
+
+fn is_blank(line: &str) -> bool {
+    leading_whitespace(line).len() == line.len()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_measure_indent() {
+        assert_eq!(leading_whitespace("  \tfoo bar"), "  \t");
+        assert_eq!(leading_whitespace("   "), "   ");
+        assert_eq!(indent_width("  \tfoo", 4), 4);
+        assert_eq!(indent_width("\t\t  foo", 4), 10);
+        assert_eq!(indent_width("foo", 4), 0);
+    }
+
+    #[test]
+    fn should_make_indent() {
+        assert_eq!(make_indent(6, 4, true), "\t  ");
+        assert_eq!(make_indent(6, 4, false), "      ");
+        assert_eq!(make_indent(6, 0, true), "      ");
+    }
+
+    #[test]
+    fn should_retab_line() {
+        assert_eq!(retab("        foo\tbar", 4, true), "\t\tfoo\tbar");
+        assert_eq!(retab("\t  foo\tbar", 4, false), "      foo\tbar");
+        assert_eq!(retab("foo", 4, true), "foo");
+    }
+
+    #[test]
+    fn should_expand_tabs() {
+        assert_eq!(expand_tabs("\tfoo\tbar", 4), "    foo bar");
+        assert_eq!(expand_tabs("  \tfoo", 4), "    foo");
+        // wide and multi-byte chars are measured by their display width
+        assert_eq!(expand_tabs("日本\tx", 4), "日本    x");
+        assert_eq!(expand_tabs("é\tx", 4), "é   x");
+        assert_eq!(expand_tabs("foo", 4), "foo");
+    }
+
+    #[test]
+    fn should_dedent_line() {
+        assert_eq!(dedent("\t\tfoo", 4), "\tfoo");
+        assert_eq!(dedent("      foo", 4), "  foo");
+        assert_eq!(dedent("  \tfoo", 4), "foo");
+        assert_eq!(dedent("  foo", 4), "foo");
+        assert_eq!(dedent("foo", 4), "foo");
+        assert_eq!(dedent("  foo", 0), "  foo");
+    }
+
+    #[test]
+    fn should_find_parent_and_sibling_rows() {
+        let lines: Vec<String> = ["a:", "  b:", "    c", "", "  d:", "    e", "f"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(parent_row(&lines, 2, 4), Some(1));
+        assert_eq!(parent_row(&lines, 4, 4), Some(0));
+        assert_eq!(parent_row(&lines, 0, 4), None);
+        assert_eq!(next_sibling_row(&lines, 1, 4), Some(4));
+        assert_eq!(next_sibling_row(&lines, 0, 4), Some(6));
+        assert_eq!(next_sibling_row(&lines, 2, 4), None);
+        assert_eq!(next_sibling_row(&lines, 6, 4), None);
+    }
+
+    #[test]
+    fn should_reindent_col() {
+        assert_eq!(reindent_col("        foo", "\t\tfoo", 9), 3);
+        assert_eq!(reindent_col("        foo", "\t\tfoo", 5), 2);
+        assert_eq!(reindent_col("\tfoo", "    foo", 0), 0);
+        assert_eq!(reindent_col("  foo", "  // foo", 3), 6);
+        assert_eq!(reindent_col("  // foo", "  foo", 3), 2);
+    }
+}
@@ -24,61 +24,181 @@
 //!
 //! - `clipboard` enables system clipboard support
 //! - `search` enables the string search in the textarea
+//! - `serde` derives `Serialize` and `Deserialize` for `TextAreaSnapshot`, to persist the editing session
 //!
 //! ## Component API
 //!
 //! **Commands**:
 //!
-//! | Cmd                                            | Result         | Behaviour                               |
-//! |------------------------------------------------|----------------|-----------------------------------------|
-//! | `Custom($TEXTAREA_CMD_NEWLINE)`                | `None`         | Insert newline                          |
-//! | `Custom($TEXTAREA_CMD_DEL_LINE_BY_END)`        | `None`         | Delete line by end to current position  |
-//! | `Custom($TEXTAREA_CMD_DEL_LINE_BY_HEAD)`       | `None`         | Delete line by head to current position |
-//! | `Custom($TEXTAREA_CMD_DEL_WORD)`               | `None`         | Delete the current word                 |
-//! | `Custom($TEXTAREA_CMD_DEL_NEXT_WORD)`          | `None`         | Delete the next word                    |
-//! | `Custom($TEXTAREA_CMD_MOVE_WORD_FORWARD)`      | `None`         | Move to the next word                   |
-//! | `Custom($TEXTAREA_CMD_MOVE_WORD_BACK)`         | `None`         | Move to the previous word               |
-//! | `Custom($TEXTAREA_CMD_MOVE_PARAGRAPH_BACK)`    | `None`         | Move to the previous paragraph          |
-//! | `Custom($TEXTAREA_CMD_MOVE_PARAGRAPH_FORWARD)` | `None`         | Move to the next paragraph              |
-//! | `Custom($TEXTAREA_CMD_MOVE_TOP)`               | `None`         | Move to the beginning of the file       |
-//! | `Custom($TEXTAREA_CMD_MOVE_BOTTOM)`            | `None`         | Move to the end of the file             |
-//! | `Custom($TEXTAREA_CMD_UNDO)`                   | `None`         | Undo last change                        |
-//! | `Custom($TEXTAREA_CMD_REDO)`                   | `None`         | Redo last change                        |
-//! | `Custom($TEXTAREA_CMD_PASTE)`                  | `None`         | Paste the current content of the buffer |
-//! | `Custom($TEXTAREA_CMD_SEARCH_BACK)`            | `None`         | Go to the previous search match         |
-//! | `Custom($TEXTAREA_CMD_SEARCH_FORWARD)`         | `None`         | Go to the next search match             |
-//! | `Cancel`                                       | `None`         | Delete next char                        |
-//! | `Delete`                                       | `None`         | Delete previous char                    |
-//! | `GoTo(Begin)`                                  | `None`         | Go to the head of the line              |
-//! | `GoTo(End)`                                    | `None`         | Go to the end of the line               |
-//! | `Move(Down)`                                   | `None`         | Move to the line below                  |
-//! | `Move(Up)`                                     | `None`         | Move to the line above                  |
-//! | `Move(Left)`                                   | `None`         | Move cursor to the left                 |
-//! | `Move(Right)`                                  | `None`         | Move cursor to the right                |
-//! | `Scroll(Up)`                                   | `None`         | Move by scroll_step lines up            |
-//! | `Scroll(Down)`                                 | `None`         | Move by scroll_step lines down          |
-//! | `Type(ch)`                                     | `None`         | Type a char in the editor               |
-//! | `Submit`                                       | `Submit`       | Get current lines                       |
+//! | Cmd                                              | Result         | Behaviour                                  |
+//! |--------------------------------------------------|----------------|--------------------------------------------|
+//! | `Custom($TEXTAREA_CMD_NEWLINE)`                  | `None`         | Insert newline                             |
+//! | `Custom($TEXTAREA_CMD_DEL_LINE_BY_END)`          | `None`         | Delete line by end to current position     |
+//! | `Custom($TEXTAREA_CMD_DEL_LINE_BY_HEAD)`         | `None`         | Delete line by head to current position    |
+//! | `Custom($TEXTAREA_CMD_DEL_WORD)`                 | `None`         | Delete the current word                    |
+//! | `Custom($TEXTAREA_CMD_DEL_NEXT_WORD)`            | `None`         | Delete the next word                       |
+//! | `Custom($TEXTAREA_CMD_DEL_TO_BOTTOM)`            | `None`         | Delete from the cursor to the end          |
+//! | `Custom($TEXTAREA_CMD_DEL_TO_TOP)`               | `None`         | Delete from the start to the cursor        |
+//! | `Custom($TEXTAREA_CMD_MOVE_WORD_FORWARD)`        | `None`         | Move to the next word                      |
+//! | `Custom($TEXTAREA_CMD_MOVE_WORD_BACK)`           | `None`         | Move to the previous word                  |
+//! | `Custom($TEXTAREA_CMD_MOVE_PARAGRAPH_BACK)`      | `None`         | Move to the previous paragraph             |
+//! | `Custom($TEXTAREA_CMD_MOVE_PARAGRAPH_FORWARD)`   | `None`         | Move to the next paragraph                 |
+//! | `Custom($TEXTAREA_CMD_MOVE_TOP)`                 | `None`         | Move to the beginning of the file          |
+//! | `Custom($TEXTAREA_CMD_MOVE_BOTTOM)`              | `None`         | Move to the end of the file                |
+//! | `Custom($TEXTAREA_CMD_PAGE_UP)`                  | `None`         | Move up by the rendered height             |
+//! | `Custom($TEXTAREA_CMD_PAGE_DOWN)`                | `None`         | Move down by the rendered height           |
+//! | `Custom($TEXTAREA_CMD_MATCH_BRACKET)`            | `None`         | Move to the matching bracket               |
+//! | `Custom($TEXTAREA_CMD_UNDO)`                     | `None`         | Undo last change                           |
+//! | `Custom($TEXTAREA_CMD_REDO)`                     | `None`         | Redo last change                           |
+//! | `Custom($TEXTAREA_CMD_PASTE)`                    | `None`         | Paste the current content of the buffer    |
+//! | `Custom($TEXTAREA_CMD_INSERT_STR)`               | `None`         | Insert the text of the insert str property |
+//! | `Custom($TEXTAREA_CMD_COPY)`                     | `None`         | Copy the selection or the current line     |
+//! | `Custom($TEXTAREA_CMD_CUT)`                      | `None`         | Cut the selection or the current line      |
+//! | `Custom($TEXTAREA_CMD_YANK_TO_REGISTER)`         | `None`         | Copy the selection or line to the register |
+//! | `Custom($TEXTAREA_CMD_PASTE_FROM_REGISTER)`      | `None`         | Insert the text of the register            |
+//! | `Custom($TEXTAREA_CMD_SELECT_START)`             | `None`         | Start selecting from the cursor            |
+//! | `Custom($TEXTAREA_CMD_SELECT_CANCEL)`            | `None`         | Clear the selection                        |
+//! | `Custom($TEXTAREA_CMD_SELECT_ALL)`               | `None`         | Select the whole text                      |
+//! | `Custom($TEXTAREA_CMD_DELETE_SELECTION)`         | `None`         | Delete the selected text                   |
+//! | `Custom($TEXTAREA_CMD_CLEAR)`                    | `None`         | Empty the whole text                       |
+//! | `Custom($TEXTAREA_CMD_DUPLICATE_LINE)`           | `None`         | Duplicate the current line                 |
+//! | `Custom($TEXTAREA_CMD_DUPLICATE_SELECTION)`      | `None`         | Duplicate the selection or the line        |
+//! | `Custom($TEXTAREA_CMD_JOIN_LINES)`               | `None`         | Join the current line with the next one    |
+//! | `Custom($TEXTAREA_CMD_TRANSPOSE_CHARS)`          | `None`         | Swap the chars around the cursor           |
+//! | `Custom($TEXTAREA_CMD_UPPERCASE)`                | `None`         | Uppercase the selection or the word        |
+//! | `Custom($TEXTAREA_CMD_LOWERCASE)`                | `None`         | Lowercase the selection or the word        |
+//! | `Custom($TEXTAREA_CMD_TOGGLE_CASE)`              | `None`         | Toggle the case of the selection or word   |
+//! | `Custom($TEXTAREA_CMD_MOVE_LINE_UP)`             | `None`         | Swap the current line with the one above   |
+//! | `Custom($TEXTAREA_CMD_MOVE_LINE_DOWN)`           | `None`         | Swap the current line with the one below   |
+//! | `Custom($TEXTAREA_CMD_SET_CURSOR)`               | `None`         | Move to the cursor position property       |
+//! | `Custom($TEXTAREA_CMD_GOTO_LINE)`                | `None`         | Move to the head of the goto line property |
+//! | `Custom($TEXTAREA_CMD_CLICK)`                    | `None`         | Move to the click position property        |
+//! | `Custom($TEXTAREA_CMD_DRAG)`                     | `None`         | Select to the click position property      |
+//! | `Custom($TEXTAREA_CMD_DRAG_END)`                 | `None`         | End the selection by mouse drag            |
+//! | `Custom($TEXTAREA_CMD_SCROLL_MOUSE)`             | `None`         | Scroll by the scroll delta property        |
+//! | `Custom($TEXTAREA_CMD_SEARCH_BACK)`              | `None`         | Go to the previous search match            |
+//! | `Custom($TEXTAREA_CMD_SEARCH_FORWARD)`           | `None`         | Go to the next search match                |
+//! | `Custom($TEXTAREA_CMD_REPLACE_NEXT)`             | `None`         | Replace the current search match           |
+//! | `Custom($TEXTAREA_CMD_REPLACE_ALL)`              | `None`         | Replace all the search matches             |
+//! | `Custom($TEXTAREA_CMD_RETAB_SELECTION)`          | `None`         | Rewrite indentation of selected lines      |
+//! | `Custom($TEXTAREA_CMD_EXPAND_TABS)`              | `None`         | Replace all tabs with spaces               |
+//! | `Custom($TEXTAREA_CMD_TABIFY)`                   | `None`         | Rewrite indentation of all lines with tabs |
+//! | `Custom($TEXTAREA_CMD_INDENT)`                   | `None`         | Indent current or selected lines           |
+//! | `Custom($TEXTAREA_CMD_INSERT_HARD_TAB)`          | `None`         | Insert a tab char, even with soft tabs     |
+//! | `Custom($TEXTAREA_CMD_DEDENT)`                   | `None`         | Dedent current or selected lines           |
+//! | `Custom($TEXTAREA_CMD_TOGGLE_COMMENT)`           | `None`         | Comment or uncomment selected lines        |
+//! | `Custom($TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT)`     | `None`         | Wrap or unwrap selection in block comment  |
+//! | `Custom($TEXTAREA_CMD_TRIM_TRAILING_WHITESPACE)` | `None`         | Remove trailing whitespace from all lines  |
+//! | `Custom($TEXTAREA_CMD_MOVE_TO_PARENT_INDENT)`    | `None`         | Move to the parent line by indentation     |
+//! | `Custom($TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING)`     | `None`         | Move to the next line at same indentation  |
+//! | `Cancel`                                         | `None`         | Delete next char                           |
+//! | `Delete`                                         | `None`         | Delete previous char                       |
+//! | `GoTo(Begin)`                                    | `None`         | Go to the head of the line                 |
+//! | `GoTo(End)`                                      | `None`         | Go to the end of the line                  |
+//! | `Move(Down)`                                     | `None`         | Move to the line below                     |
+//! | `Move(Up)`                                       | `None`         | Move to the line above                     |
+//! | `Move(Left)`                                     | `None`         | Move cursor to the left                    |
+//! | `Move(Right)`                                    | `None`         | Move cursor to the right                   |
+//! | `Scroll(Up)`                                     | `None`         | Move by scroll_step lines up               |
+//! | `Scroll(Down)`                                   | `None`         | Move by scroll_step lines down             |
+//! | `Type(ch)`                                       | `None`         | Type a char in the editor                  |
+//! | `Submit`                                         | `Submit`       | Get current lines                          |
 //!
-//! > ❗ Paste command is supported only if the `clipboard` feature is enabled
+//! > ❗ Copy, cut and paste commands are supported only if the `clipboard` feature is enabled
+//!
+//! > ❗ Search and replace commands are supported only if the `search` feature is enabled
+//!
+//! > ❗ Without a selection, copy and cut commands take the whole current line, including its line break
+//!
+//! > ❗ While selecting, `Move`, `GoTo` and `Scroll` commands extend the selection up to the cursor
+//!
+//! > ❗ While text is selected, `Type`, `Delete` and `Cancel` commands replace or delete the selected text, in a single history step
+//!
+//! > ❗ Page commands move by the height of the last render minus one row; before the first render they move by `ScrollStep`
+//!
+//! > ❗ `TEXTAREA_CMD_TRANSPOSE_CHARS` swaps the char before the cursor with the one at the cursor and moves after them; at the end of the line it swaps the last two chars, as in Emacs, while at the head of the line it does nothing
+//!
+//! > ❗ Registers are kept by the textarea, so they work without the `clipboard` feature and don't touch the system clipboard
 //!
 //! **State**: the state returned is a `Vec(String)` containing the lines in the text area.
 //!
 //! **Properties**:
 //!
 //! - `Borders(Borders)`: set borders properties for component
+//! - `Custom($TEXTAREA_AUTO_CLOSE, Flag)`: Insert the closer of the brackets and quotes typed, after the cursor, except for quotes typed right after a word. Typing the closer just inserted types over it, while deleting its opener deletes both
+//! - `Custom($TEXTAREA_AUTO_CLOSE_PAIRS, String)`: Set the pairs closed by `TEXTAREA_AUTO_CLOSE`, made of each opener followed by its closer. Default: `()[]{}""''`
+//! - `Custom($TEXTAREA_BLOCK_COMMENT, Payload(Tup2(Str, Str)))`: Set the pair which opens and closes a block comment, such as `/* ` and ` */`, used by `TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT`. Without a selection, the cursor line is toggled
+//! - `Custom($TEXTAREA_CENTER_CURSOR, Flag)`: Keep the cursor row in the middle of the view while moving, except near the start and the end of the text
 //! - `Custom($TREE_IDENT_SIZE, Size)`: Set space to render for each each depth level
 //! - `Custom($TEXTAREA_MAX_HISTORY, Payload(One(Usize)))`: Set the history steps to record
+//! - `Custom($TEXTAREA_MAX_LENGTH, Length)`: Set the maximum amount of characters, counting line breaks as one
+//! - `Custom($TEXTAREA_MAX_LINES, Length)`: Set the maximum amount of lines
+//! - `Custom($TEXTAREA_MAX_WIDTH, Size)`: Set the maximum width of the content inside the layout margins; in a wider area the content is centered
 //! - `Custom($TEXTAREA_CURSOR_STYLE, Style)`: Set the cursor style
+//! - `Custom($TEXTAREA_UNFOCUSED_CURSOR_STYLE, Style)`: Set the cursor style while not focused (default: hidden)
+//! - `Custom($TEXTAREA_CLICK_POSITION, Payload(Tup2(Usize, Usize)))`: Set the terminal column and row of a mouse click or drag, to move to with `TEXTAREA_CMD_CLICK` or to select to with `TEXTAREA_CMD_DRAG`; positions outside of the text are ignored
+//! - `Custom($TEXTAREA_COMMENT_PREFIX, String)`: Set the prefix which comments a line, such as `// `, used by `TEXTAREA_CMD_TOGGLE_COMMENT`
+//! - `Custom($TEXTAREA_CURSOR_POSITION, Payload(Tup2(Usize, Usize)))`: Set the row and the column to move to with `TEXTAREA_CMD_SET_CURSOR`
+//! - `Custom($TEXTAREA_CURRENT_LINE_NUMBER_STYLE, Style)`: Alias of `TEXTAREA_CURSOR_GUTTER_STYLE`
+//! - `Custom($TEXTAREA_CURSOR_GUTTER_STYLE, Style)`: Set the style patched on the line number of the cursor row, such as to emphasize the current line number as the cursor moves
 //! - `Custom($TEXTAREA_CURSOR_LINE_STYLE, Style)`: Set the current line style
+//! - `Custom($TEXTAREA_DISABLED_CMDS, Payload(Vec(Str)))`: Set the `TEXTAREA_CMD_*` ids of the commands to ignore, such as undo and paste; unlike `TEXTAREA_READONLY`, the other commands still edit the text
+//! - `Custom($TEXTAREA_ENTER_SUBMITS, Flag)`: In single line mode, make `TEXTAREA_CMD_NEWLINE` and `Type('\n')` return `Submit` with the state, as a form field; multi line mode is unaffected
+//! - `Custom($TEXTAREA_FILENAME, String)`: Set the name of the edited file, displayed by the `{FILENAME}` key of the status and footer format
+//! - `Custom($TEXTAREA_FOCUS_CURSOR_BEHAVIOR, String)`: Set where the cursor is moved when the textarea gains focus, such as `FocusCursorBehavior::ToEnd.as_str()`. Default: `keep`, which leaves it where it was
 //! - `Custom($TEXTAREA_FOOTER_FMT, Payload(Tup2(Str, Style)))`: Set the format and the style for the footer bar
+//! - `Custom($TEXTAREA_FOOTER_HEIGHT, Size)`: Set the amount of rows of the footer bar; a footer taller than one row wraps its text. Default: 1
+//! - `Custom($TEXTAREA_GOTO_LINE, Payload(One(Usize)))`: Set the line number, starting from 1, to move to with `TEXTAREA_CMD_GOTO_LINE`
+//! - `Custom($TEXTAREA_HIGHLIGHT_WORD, Flag)`: Highlight the whole-word occurrences of the word at the cursor in the visible rows; nothing is highlighted while the cursor is on whitespace or punctuation
+//! - `Custom($TEXTAREA_HIGHLIGHT_WORD_STYLE, Style)`: Set the style patched on the occurrences of the word at the cursor. Default: underlined
+//! - `Custom($TEXTAREA_LINE_ENDING, String)`: Set the line ending which joins the lines of `TextArea::text`, such as `LineEnding::CrLf.as_str()`. Default: `\n`
+//! - `Custom($TEXTAREA_TRIM_TRAILING_NEWLINE, Flag)`: Leave a trailing empty line out of `TextArea::text`, without changing the lines in the textarea. Default: `false`
+//! - `Custom($TEXTAREA_INDENT_GUIDES, Flag)`: Draw vertical guides at the tab stops in the indentation of the lines
+//! - `Custom($TEXTAREA_INDENT_GUIDE_STYLE, Style)`: Set the style of the indent guides. Default: dimmed
+//! - `Custom($TEXTAREA_INSERT_STR, String)`: Set the text to insert at the cursor with `TEXTAREA_CMD_INSERT_STR`, such as a snippet; line breaks split it into lines
+//! - `Custom($TEXTAREA_INVALID, Flag)`: Mark the content as invalid, such as with `TextArea::mark_invalid` after validating a form field
+//! - `Custom($TEXTAREA_INVALID_STYLE, Style)`: Set the style of the borders while the textarea is focused and invalid. Default: red
+//! - `Custom($TEXTAREA_LAYOUT_MARGIN, Size)`: Set the margin around the content, on all sides, or `Payload(Tup2(U16, U16))` for the horizontal and the vertical margins. Default: 1 with borders, 0 otherwise
+//! - `Custom($TEXTAREA_JOIN_SEPARATOR, String)`: Set the separator inserted between the lines joined by `TEXTAREA_CMD_JOIN_LINES`. Default: a space
 //! - `Custom($TEXTAREA_LINE_NUMBER_STYLE, Style)`: set the style for the line number
+//! - `Custom($TEXTAREA_LINE_NUMBER_WIDTH, Size)`: Set the minimum amount of columns of the line numbers, so that the gutter keeps its width while lines are added; wider numbers still expand it
+//! - `Custom($TEXTAREA_LINE_WRAP, Flag)`: Wrap the lines longer than the textarea on the next rows, instead of scrolling horizontally. Line numbers are displayed per logical line, on its first row only
+//! - `Custom($TEXTAREA_WRAP_INDENT, Flag)`: Indent the rows continuing a wrapped line as its leading whitespace. Only applies if line wrap is enabled
+//! - `Custom($TEXTAREA_MASK_CHAR, String)`: Display its first char in place of each char of the text, such as `*` for a password field, while the state holds the real text. The word and bracket highlights and the copy and cut commands are disabled while masked. An empty string removes the mask
+//! - `Custom($TEXTAREA_MIN_VISIBLE_LINES, Length)`: Set the minimum amount of rows of text counted by `TextArea::desired_height`
+//! - `Custom($TEXTAREA_MAX_VISIBLE_LINES, Length)`: Set the maximum amount of rows of text counted by `TextArea::desired_height`; more text is scrolled
+//! - `Custom($TEXTAREA_MATCH_BRACKETS, Flag)`: Highlight the bracket at the cursor, or before it, along with its matching one, even on another line
+//! - `Custom($TEXTAREA_MATCH_BRACKET_STYLE, Style)`: Set the style of the matching brackets. Default: bold and underlined
+//! - `Custom($TEXTAREA_MODIFIED_MARKER, String)`: Set the marker displayed by the `{MODIFIED}` key of the status and footer format. Default: `[+]`
+//! - `Custom($TEXTAREA_PLACEHOLDER, Payload(Tup2(Str, Style)))`: Set the placeholder text and its style, displayed while the textarea is empty
+//! - `Custom($TEXTAREA_RELATIVE_LINE_NUMBERS, Flag)`: Display the line numbers as the distance from the cursor row, which displays its own line number
 //! - `Custom($TEXTAREA_STATUS_FMT, Payload(Tup2(Str, Style)))`: Set the format and the style for the status bar
+//! - `Custom($TEXTAREA_STATUS_HEIGHT, Size)`: Set the amount of rows of the status bar; a status taller than one row wraps its text. Default: 1
+//! - `Custom($TEXTAREA_TAB_CHAR, String)`: Draw its first char, such as `▸`, on the first column of the tabs, with the whitespace style, even if `TEXTAREA_SHOW_WHITESPACE` is disabled; the text is not changed
 //! - `Custom($TEXTAREA_SEARCH_PATTERN, String`: Set search pattern
 //! - `Custom($TEXTAREA_SEARCH_STYLE, Style`: Set search style
-//! - `Custom($TEXTAREA_SINGLE_LINE, Style`: Act as single-line input
+//! - `Custom($TEXTAREA_SEARCH_OPTIONS, Payload(Tup4(Bool, Bool, Bool, Bool)))`: Set search options: regex, case insensitive, whole word and wrap around
+//! - `Custom($TEXTAREA_SEARCH_CASE_INSENSITIVE, Flag)`: Match the search pattern ignoring case
+//! - `Custom($TEXTAREA_SEARCH_REGEX, Flag)`: Treat the search pattern as a regex (default), otherwise match it literally
+//! - `Custom($TEXTAREA_SEARCH_CENTER, Flag)`: Scroll the line of the match found by `TEXTAREA_CMD_SEARCH_FORWARD` and `TEXTAREA_CMD_SEARCH_BACK` to the middle of the view
+//! - `Custom($TEXTAREA_REPLACE_WITH, String)`: Set the text replacing the search matches; a regex pattern can refer to its groups, such as `$1`
+//! - `Custom($TEXTAREA_SCROLL_DELTA, Payload(One(Isize)))`: Set the amount of scroll steps, negative upwards, to scroll the view by with `TEXTAREA_CMD_SCROLL_MOUSE`, such as on mouse wheel events. The cursor doesn't move, and is brought back into view by the next command
+//! - `Custom($TEXTAREA_SCROLL_OFF, Length)`: Set the amount of rows and columns of context kept visible around the cursor when scrolling, up to half of the textarea. Default: 0
+//! - `Custom($TEXTAREA_SCROLLBAR, Flag)`: Show a scrollbar on the right of the text while it doesn't fit the area, drawn over the right border if any
+//! - `Custom($TEXTAREA_SCROLLBAR_STYLE, Style)`: Set the style of the scrollbar
+//! - `Custom($TEXTAREA_SHOW_LINE_NUMBERS, Flag)`: Show or hide the line numbers, regardless of their style. If unset, the line numbers are shown once their style is set
+//! - `Custom($TEXTAREA_SHOW_WHITESPACE, Flag)`: Draw the leading and trailing spaces and the tabs as visible glyphs, styled as the line numbers or dimmed
+//! - `Custom($TEXTAREA_SINGLE_LINE, Flag)`: Act as single-line input, where line breaks are not inserted and vertical moves are ignored
+//! - `Custom($TEXTAREA_SMART_TAB, Flag)`: Make `Type('\t')` indent the selected lines, or the cursor line when the cursor is in its indentation, instead of inserting a tab
+//! - `Custom($TEXTAREA_READONLY, Flag)`: Reject all the commands which change the text
+//! - `Custom($TEXTAREA_REGISTER, String)`: Set the name of the register, its first char, used by `TEXTAREA_CMD_YANK_TO_REGISTER` and `TEXTAREA_CMD_PASTE_FROM_REGISTER`. Default: the unnamed register `"`, which also holds the last yanked text
+//! - `Custom($TEXTAREA_RULER_COLUMN, Size)`: Draw a vertical ruler at the display column, starting from 1, such as 81 to mark the text past 80 columns, or `Payload(Vec(U16))` for many rulers
+//! - `Custom($TEXTAREA_RULER_STYLE, Style)`: Set the style of the rulers, patched on the text they cross. Default: dimmed
 //! - `Style(Style)`: Set the general style for the textarea
 //! - `Custom($TEXTAREA_TAB_SIZE, Size)`: Set the tab size to display
+//! - `Custom($TEXTAREA_UNDO_COALESCE, Flag)`: Undo the chars typed in a row as a single step, up to the end of a word or to a cursor movement. Default: each char is undone by itself
+//! - `Custom($TEXTAREA_UNMATCHED_BRACKET_STYLE, Style)`: Set the style of the bracket at the cursor when it has no match; if unset, unmatched brackets are not highlighted
+//! - `Custom($TEXTAREA_WHITESPACE_CHARS, Payload(Tup2(Str, Str)))`: Set the glyphs drawn in place of spaces and tabs when whitespace is shown. Default: `·` and `→`
 //! - `FocusStyle(Style)`: inactive style
 //! - `ScrollStep(Length)`: Defines the maximum amount of rows to scroll
 //! - `Title(Title)`: Set box title
@@ -88,12 +208,29 @@
 //! The status and footer bars support a special syntax. The following keys can be inserted into the string:
 //!
 //! - `{ROW}`: current row
-//! - `{COL}`: current column
+//! - `{COL}`: current display column, where wide chars take two columns and tabs reach the next tab stop
+//! - `{COL_BYTES}`: current byte offset in the line, starting from 1
+//! - `{LINES}`: total amount of lines
+//! - `{MODIFIED}`: marker displayed while the text has unsaved changes, `[+]` by default
+//! - `{FILENAME}`: name of the edited file set with `TEXTAREA_FILENAME`, or `[No Name]` if unset
+//! - `{CHARS}`: total amount of characters
+//! - `{WORDS}`: total amount of words
+//! - `{PERCENT}`: how far the cursor row is through the text, as a percentage
+//! - `{TIME}`: current time, formatted by the callback set with `TextArea::set_time_fmt`; empty if unset
+//! - `{SEARCH}`: position of the current search match and amount of matches, such as `[3/17]`; requires the `search` feature
+//! - `{TABSIZE}`: tab length set with `TEXTAREA_TAB_SIZE`
+//! - `{INDENT_MODE}`: `Tab` if indenting inserts tabs, set with `TEXTAREA_HARD_TAB`, or `Spaces`
+//!
+//! Other keys can be registered with `TextArea::register_fmt_key`, passing a closure which formats the key from the textarea widget.
+//! Builtin keys are plain functions, while custom keys are closures called through an `Arc` on every render: keep them cheap, returning a value computed elsewhere rather than querying it (e.g. the git branch).
+//!
+//! > ❗ The `{TIME}` key changes over time, while the textarea is only drawn when the application redraws: if it redraws on state changes only, the clock is updated only then, so redraw it on a tick to keep the time current
 //!
 //! ## Example
 //!
 //! ```rust
-//! use std::{fs, io::{self, BufRead}};
+//! use std::fs::File;
+//! use std::io::BufReader;
 //! use tuirealm::{
 //!     application::PollStrategy,
 //!     command::{Cmd, CmdResult, Direction, Position},
@@ -105,21 +242,17 @@
 //! };
 //! use tui_realm_textarea::TextArea;
 //!
-//! let textarea = match fs::File::open("README.md") {
-//!     Ok(reader) => TextArea::new(
-//!         io::BufReader::new(reader)
-//!             .lines()
-//!             .map(|l| l.unwrap())
-//!             .collect::<_>(),
-//!     ),
-//!     Err(_) => TextArea::default(),
-//! };
+//! let textarea = File::open("README.md")
+//!     .map(BufReader::new)
+//!     .and_then(TextArea::from_reader)
+//!     .unwrap_or_default();
 //! let component = textarea
 //!     .borders(
 //!         Borders::default()
 //!             .color(Color::LightYellow)
 //!             .modifiers(BorderType::Double),
 //!     )
+//!     .cursor_gutter_style(Style::default().add_modifier(TextModifiers::BOLD))
 //!     .cursor_line_style(Style::default())
 //!     .cursor_style(Style::default().add_modifier(TextModifiers::REVERSED))
 //!     .footer_bar("Press <ESC> to quit", Style::default())
@@ -142,8 +275,29 @@
 #![doc(html_playground_url = "https://play.rust-lang.org")]
 
 // -- internal
+mod bracket;
+mod comment;
 mod fmt;
+mod focus_cursor;
+mod history;
+mod indent;
+mod line_ending;
+mod render;
+#[cfg(feature = "search")]
+mod search;
+mod snapshot;
+mod word;
+mod wrap;
 use fmt::LineFmt;
+pub use fmt::{CustomFmtCallback, TimeFmtCallback};
+pub use focus_cursor::FocusCursorBehavior;
+use history::{Edit, History};
+pub use line_ending::LineEnding;
+pub use render::LineStyler;
+use render::{Renderer, Viewport};
+#[cfg(feature = "search")]
+pub use search::SearchOptions;
+pub use snapshot::TextAreaSnapshot;
 
 // deps
 
@@ -152,29 +306,102 @@ extern crate lazy_regex;
 
 #[cfg(feature = "clipboard")]
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::ops::Range;
 use tui_textarea::{CursorMove, TextArea as TextAreaWidget};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{
-    Alignment, AttrValue, Attribute, Borders, PropPayload, PropValue, Props, Style, TextModifiers,
+    Alignment, AttrValue, Attribute, BorderSides, Borders, Color, PropPayload, PropValue, Props,
+    Style, TextModifiers,
 };
 use tuirealm::ratatui::layout::{Constraint, Direction as LayoutDirection, Layout, Rect};
-use tuirealm::ratatui::widgets::{Block, Paragraph};
+use tuirealm::ratatui::widgets::{
+    Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+};
 use tuirealm::{Frame, MockComponent, State, StateValue};
 
 // -- props
+pub const TEXTAREA_AUTO_CLOSE: &str = "auto-close";
+pub const TEXTAREA_AUTO_CLOSE_PAIRS: &str = "auto-close-pairs";
+pub const TEXTAREA_CENTER_CURSOR: &str = "center-cursor";
+pub const TEXTAREA_CLICK_POSITION: &str = "click-position";
+pub const TEXTAREA_COMMENT_PREFIX: &str = "comment-prefix";
+pub const TEXTAREA_BLOCK_COMMENT: &str = "block-comment";
+pub const TEXTAREA_CURRENT_LINE_NUMBER_STYLE: &str = TEXTAREA_CURSOR_GUTTER_STYLE;
+pub const TEXTAREA_CURSOR_POSITION: &str = "cursor-position";
+pub const TEXTAREA_CURSOR_GUTTER_STYLE: &str = "cursor-gutter-style";
 pub const TEXTAREA_CURSOR_LINE_STYLE: &str = "cursor-line-style";
 pub const TEXTAREA_CURSOR_STYLE: &str = "cursor-style";
+pub const TEXTAREA_DISABLED_CMDS: &str = "disabled-cmds";
+pub const TEXTAREA_GOTO_LINE: &str = "goto-line";
+pub const TEXTAREA_ENTER_SUBMITS: &str = "enter-submits";
+pub const TEXTAREA_FILENAME: &str = "filename";
+pub const TEXTAREA_FOCUS_CURSOR_BEHAVIOR: &str = "focus-cursor-behavior";
+pub const TEXTAREA_FOOTER_HEIGHT: &str = "footer-height";
 pub const TEXTAREA_FOOTER_FMT: &str = "footer-fmt";
+pub const TEXTAREA_LAYOUT_MARGIN: &str = "layout-margin";
+pub const TEXTAREA_LINE_ENDING: &str = "line-ending";
+pub const TEXTAREA_TRIM_TRAILING_NEWLINE: &str = "trim-trailing-newline";
+pub const TEXTAREA_LINE_NUMBER_WIDTH: &str = "line-number-width";
 pub const TEXTAREA_LINE_NUMBER_STYLE: &str = "line-number-style";
+pub const TEXTAREA_LINE_WRAP: &str = "line-wrap";
+pub const TEXTAREA_WRAP_INDENT: &str = "wrap-indent";
+pub const TEXTAREA_MASK_CHAR: &str = "mask-char";
+pub const TEXTAREA_MATCH_BRACKETS: &str = "match-brackets";
+pub const TEXTAREA_MATCH_BRACKET_STYLE: &str = "match-bracket-style";
+pub const TEXTAREA_MAX_VISIBLE_LINES: &str = "max-visible-lines";
+pub const TEXTAREA_MIN_VISIBLE_LINES: &str = "min-visible-lines";
 pub const TEXTAREA_MAX_HISTORY: &str = "max-history";
+pub const TEXTAREA_MAX_LENGTH: &str = "max-length";
+pub const TEXTAREA_MAX_LINES: &str = "max-lines";
+pub const TEXTAREA_MAX_WIDTH: &str = "max-width";
+pub const TEXTAREA_MODIFIED_MARKER: &str = "modified-marker";
+pub const TEXTAREA_PLACEHOLDER: &str = "placeholder";
+pub const TEXTAREA_STATUS_HEIGHT: &str = "status-height";
 pub const TEXTAREA_STATUS_FMT: &str = "status-fmt";
+pub const TEXTAREA_UNDO_COALESCE: &str = "undo-coalesce";
+pub const TEXTAREA_UNFOCUSED_CURSOR_STYLE: &str = "unfocused-cursor-style";
+pub const TEXTAREA_UNMATCHED_BRACKET_STYLE: &str = "unmatched-bracket-style";
+pub const TEXTAREA_TAB_CHAR: &str = "tab-char";
 pub const TEXTAREA_TAB_SIZE: &str = "tab-size";
+pub const TEXTAREA_WHITESPACE_CHARS: &str = "whitespace-chars";
 pub const TEXTAREA_HARD_TAB: &str = "hard-tab";
+pub const TEXTAREA_HIGHLIGHT_WORD: &str = "highlight-word";
+pub const TEXTAREA_HIGHLIGHT_WORD_STYLE: &str = "highlight-word-style";
+pub const TEXTAREA_INDENT_GUIDES: &str = "indent-guides";
+pub const TEXTAREA_INDENT_GUIDE_STYLE: &str = "indent-guide-style";
+pub const TEXTAREA_SHOW_WHITESPACE: &str = "show-whitespace";
+pub const TEXTAREA_SHOW_LINE_NUMBERS: &str = "show-line-numbers";
 pub const TEXTAREA_SINGLE_LINE: &str = "single-line";
+pub const TEXTAREA_SMART_TAB: &str = "smart-tab";
+pub const TEXTAREA_RELATIVE_LINE_NUMBERS: &str = "relative-line-numbers";
+pub const TEXTAREA_READONLY: &str = "read-only";
+pub const TEXTAREA_REGISTER: &str = "register";
+pub const TEXTAREA_RULER_COLUMN: &str = "ruler-column";
+pub const TEXTAREA_RULER_STYLE: &str = "ruler-style";
+#[cfg(feature = "search")]
+pub const TEXTAREA_SEARCH_CENTER: &str = "search-center";
 #[cfg(feature = "search")]
 pub const TEXTAREA_SEARCH_PATTERN: &str = "search-pattern";
 #[cfg(feature = "search")]
 pub const TEXTAREA_SEARCH_STYLE: &str = "search-style";
+#[cfg(feature = "search")]
+pub const TEXTAREA_SEARCH_OPTIONS: &str = "search-options";
+#[cfg(feature = "search")]
+pub const TEXTAREA_SEARCH_CASE_INSENSITIVE: &str = "search-case-insensitive";
+#[cfg(feature = "search")]
+pub const TEXTAREA_SEARCH_REGEX: &str = "search-regex";
+#[cfg(feature = "search")]
+pub const TEXTAREA_REPLACE_WITH: &str = "replace-with";
+pub const TEXTAREA_SCROLL_DELTA: &str = "scroll-delta";
+pub const TEXTAREA_INSERT_STR: &str = "insert-str";
+pub const TEXTAREA_INVALID: &str = "invalid";
+pub const TEXTAREA_INVALID_STYLE: &str = "invalid-style";
+pub const TEXTAREA_JOIN_SEPARATOR: &str = "join-separator";
+pub const TEXTAREA_SCROLL_OFF: &str = "scroll-off";
+pub const TEXTAREA_SCROLLBAR: &str = "scrollbar";
+pub const TEXTAREA_SCROLLBAR_STYLE: &str = "scrollbar-style";
 
 // -- cmd
 pub const TEXTAREA_CMD_NEWLINE: &str = "0";
@@ -196,17 +423,104 @@ pub const TEXTAREA_CMD_PASTE: &str = "d";
 pub const TEXTAREA_CMD_SEARCH_FORWARD: &str = "e";
 #[cfg(feature = "search")]
 pub const TEXTAREA_CMD_SEARCH_BACK: &str = "f";
+pub const TEXTAREA_CMD_RETAB_SELECTION: &str = "10";
+pub const TEXTAREA_CMD_MOVE_TO_PARENT_INDENT: &str = "11";
+pub const TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING: &str = "12";
+#[cfg(feature = "clipboard")]
+pub const TEXTAREA_CMD_COPY: &str = "13";
+#[cfg(feature = "clipboard")]
+pub const TEXTAREA_CMD_CUT: &str = "14";
+pub const TEXTAREA_CMD_SELECT_START: &str = "15";
+pub const TEXTAREA_CMD_SELECT_CANCEL: &str = "16";
+pub const TEXTAREA_CMD_SELECT_ALL: &str = "17";
+pub const TEXTAREA_CMD_DUPLICATE_LINE: &str = "18";
+pub const TEXTAREA_CMD_MOVE_LINE_UP: &str = "19";
+pub const TEXTAREA_CMD_MOVE_LINE_DOWN: &str = "1a";
+pub const TEXTAREA_CMD_SET_CURSOR: &str = "1b";
+pub const TEXTAREA_CMD_GOTO_LINE: &str = "1c";
+#[cfg(feature = "search")]
+pub const TEXTAREA_CMD_REPLACE_NEXT: &str = "1d";
+#[cfg(feature = "search")]
+pub const TEXTAREA_CMD_REPLACE_ALL: &str = "1e";
+pub const TEXTAREA_CMD_INDENT: &str = "1f";
+pub const TEXTAREA_CMD_DEDENT: &str = "20";
+pub const TEXTAREA_CMD_TOGGLE_COMMENT: &str = "21";
+pub const TEXTAREA_CMD_CLICK: &str = "22";
+pub const TEXTAREA_CMD_SCROLL_MOUSE: &str = "23";
+pub const TEXTAREA_CMD_DRAG: &str = "24";
+pub const TEXTAREA_CMD_DRAG_END: &str = "25";
+pub const TEXTAREA_CMD_TRIM_TRAILING_WHITESPACE: &str = "26";
+pub const TEXTAREA_CMD_INSERT_STR: &str = "27";
+pub const TEXTAREA_CMD_JOIN_LINES: &str = "28";
+pub const TEXTAREA_CMD_EXPAND_TABS: &str = "29";
+pub const TEXTAREA_CMD_TABIFY: &str = "2a";
+pub const TEXTAREA_CMD_DELETE_SELECTION: &str = "2b";
+pub const TEXTAREA_CMD_PAGE_UP: &str = "2c";
+pub const TEXTAREA_CMD_PAGE_DOWN: &str = "2d";
+pub const TEXTAREA_CMD_CLEAR: &str = "2e";
+pub const TEXTAREA_CMD_TRANSPOSE_CHARS: &str = "2f";
+pub const TEXTAREA_CMD_UPPERCASE: &str = "30";
+pub const TEXTAREA_CMD_LOWERCASE: &str = "31";
+pub const TEXTAREA_CMD_TOGGLE_CASE: &str = "32";
+pub const TEXTAREA_CMD_DEL_TO_BOTTOM: &str = "33";
+pub const TEXTAREA_CMD_DEL_TO_TOP: &str = "34";
+pub const TEXTAREA_CMD_YANK_TO_REGISTER: &str = "35";
+pub const TEXTAREA_CMD_PASTE_FROM_REGISTER: &str = "36";
+pub const TEXTAREA_CMD_MATCH_BRACKET: &str = "37";
+pub const TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT: &str = "38";
+pub const TEXTAREA_CMD_INSERT_HARD_TAB: &str = "39";
+pub const TEXTAREA_CMD_DUPLICATE_SELECTION: &str = "3a";
+
+/// Register holding the last yanked text
+const UNNAMED_REGISTER: char = '"';
+
+/// A callback called with the textarea whenever its text changes
+pub type ChangeCallback = Box<dyn FnMut(&TextArea)>;
 
 /// textarea tui-realm component
 pub struct TextArea<'a> {
     props: Props,
     widget: TextAreaWidget<'a>,
+    /// Undo/redo history
+    history: History,
+    /// Revision of the history when the text was last reset as not modified
+    saved: u64,
     /// Status fmt
     status_fmt: Option<LineFmt>,
     /// footer fmt
     footer_fmt: Option<LineFmt>,
+    /// Custom keys of the status and footer fmt
+    fmt_keys: HashMap<String, CustomFmtCallback>,
     /// Act as single-line input
     single_line: bool,
+    /// Reject the commands which change the text
+    read_only: bool,
+    /// Position of the text shown in the textarea
+    viewport: Viewport,
+    /// Area where the textarea has been rendered last
+    area: Rect,
+    /// Scroll the viewport to keep the cursor visible; unset while the viewport is scrolled by the mouse
+    follow_cursor: bool,
+    /// Position where the selection by mouse drag started
+    drag_anchor: Option<(usize, usize)>,
+    /// Callback styling the lines
+    line_styler: Option<LineStyler>,
+    /// Callback formatting the time of the `{TIME}` key
+    time_fmt: Option<TimeFmtCallback>,
+    /// Callback called whenever the text changes
+    on_change: Option<ChangeCallback>,
+    /// Text yanked to the registers, by name
+    registers: HashMap<char, String>,
+    /// Styles of the whole rows of the lines, by line index
+    line_highlights: HashMap<usize, Style>,
+    /// Positions of the closers inserted along with their opener, which can be typed over,
+    /// innermost last. Cleared by any command but typing and deleting chars
+    auto_closed: Vec<(usize, usize)>,
+    #[cfg(feature = "search")]
+    search_options: SearchOptions,
+    /// The system clipboard, created on first use and kept, since creating it may be slow
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<ClipboardContext>,
 }
 
 impl<'a, I> From<I> for TextArea<'a>
@@ -227,15 +541,54 @@ impl<'a> Default for TextArea<'a> {
 
 impl<'a> TextArea<'a> {
     pub fn new(lines: Vec<String>) -> Self {
+        let mut widget = TextAreaWidget::new(lines);
+        // history is kept by the component, so that commands can be undone as a single step
+        widget.set_max_histories(0);
+        let history = History::new(50);
         Self {
             props: Props::default(),
-            widget: TextAreaWidget::new(lines),
+            saved: history.revision(),
+            widget,
+            history,
             status_fmt: None,
             footer_fmt: None,
+            fmt_keys: HashMap::new(),
             single_line: false,
+            read_only: false,
+            viewport: Viewport::default(),
+            area: Rect::default(),
+            follow_cursor: true,
+            drag_anchor: None,
+            line_styler: None,
+            time_fmt: None,
+            on_change: None,
+            registers: HashMap::new(),
+            line_highlights: HashMap::new(),
+            auto_closed: Vec::new(),
+            #[cfg(feature = "search")]
+            search_options: SearchOptions::default(),
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
         }
     }
 
+    /// Instantiates a new `TextArea` with the lines of `s`, split on `\n` or `\r\n`.
+    /// An empty string makes a single empty line, as well as a trailing line break
+    pub fn from_str_lines(s: &str) -> Self {
+        Self::new(
+            s.split('\n')
+                .map(|x| x.strip_suffix('\r').unwrap_or(x).to_string())
+                .collect(),
+        )
+    }
+
+    /// Instantiates a new `TextArea` with the lines read from `reader`, split on `\n` or `\r\n`.
+    /// The lines are moved into the textarea as they are read, so the text is held only once.
+    /// Fails with the first error returned by `reader`, such as for invalid UTF-8
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        reader.lines().collect::<io::Result<_>>().map(Self::new)
+    }
+
     /// Set another style from default to use when component is inactive
     pub fn inactive(mut self, s: Style) -> Self {
         self.attr(Attribute::FocusStyle, AttrValue::Style(s));
@@ -248,6 +601,15 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set the style of the borders while the textarea is focused and marked as invalid. Default: red
+    pub fn invalid_style(mut self, s: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_INVALID_STYLE),
+            AttrValue::Style(s),
+        );
+        self
+    }
+
     /// Set widget title
     pub fn title<S: AsRef<str>>(mut self, t: S, a: Alignment) -> Self {
         self.attr(
@@ -257,12 +619,74 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Place the cursor at `row` and `col`, clamped to the text, such as to open a file at a search result.
+    /// The cursor is scrolled into view on the first render
+    pub fn cursor_at(mut self, row: usize, col: usize) -> Self {
+        self.jump((row, col));
+        self
+    }
+
     /// Set scroll step for scrolling command
     pub fn scroll_step(mut self, step: usize) -> Self {
         self.attr(Attribute::ScrollStep, AttrValue::Length(step));
         self
     }
 
+    /// Set the amount of rows and columns of context kept visible around the cursor when scrolling,
+    /// up to half of the textarea. Default: 0
+    pub fn scroll_off(mut self, scroll_off: usize) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SCROLL_OFF),
+            AttrValue::Length(scroll_off),
+        );
+        self
+    }
+
+    /// Set whether to show a scrollbar on the right of the text, while the text doesn't fit the area,
+    /// and its style. The scrollbar is drawn over the right border if any
+    pub fn scrollbar(mut self, enabled: bool, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SCROLLBAR),
+            AttrValue::Flag(enabled),
+        );
+        self.attr(
+            Attribute::Custom(TEXTAREA_SCROLLBAR_STYLE),
+            AttrValue::Style(style),
+        );
+        self
+    }
+
+    /// Set whether to keep the cursor row in the middle of the view while moving, except near the start
+    /// and the end of the text
+    pub fn center_cursor(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_CENTER_CURSOR),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set whether the chars typed in a row are undone as a single step, up to the end of a word or
+    /// to a cursor movement. Default: false, each char is undone by itself
+    pub fn undo_coalesce(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_UNDO_COALESCE),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set a callback called whenever a command or `insert_str` changes the text, such as to validate
+    /// or to save it. It isn't called by the commands which don't change the text, nor by `set_lines`.
+    ///
+    /// The callback gets the textarea while it's being changed, so it can only read it: to act on
+    /// the textarea or on the application, share the state with it, such as through an
+    /// `Rc<RefCell<_>>`, or send a message from it
+    pub fn on_change(mut self, f: ChangeCallback) -> Self {
+        self.on_change = Some(f);
+        self
+    }
+
     /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
     pub fn max_histories(mut self, max: usize) -> Self {
         self.attr(
@@ -272,6 +696,54 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set the maximum amount of characters in the textarea, counting line breaks as one character.
+    /// Edits which would exceed it are rejected, while pasted text is truncated to fit
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MAX_LENGTH),
+            AttrValue::Length(max),
+        );
+        self
+    }
+
+    /// Set the maximum amount of lines in the textarea.
+    /// Edits which would exceed it are rejected, while pasted text is truncated to fit
+    pub fn max_lines(mut self, max: usize) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MAX_LINES),
+            AttrValue::Length(max),
+        );
+        self
+    }
+
+    /// Set the minimum amount of rows of text counted by `desired_height`
+    pub fn min_visible_lines(mut self, min: usize) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MIN_VISIBLE_LINES),
+            AttrValue::Length(min),
+        );
+        self
+    }
+
+    /// Set the maximum amount of rows of text counted by `desired_height`, past which the text scrolls
+    pub fn max_visible_lines(mut self, max: usize) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MAX_VISIBLE_LINES),
+            AttrValue::Length(max),
+        );
+        self
+    }
+
+    /// Set the maximum width of the content inside the layout margins, such as for a distraction-free
+    /// writing column. A wider area is left blank on both sides of the content, which is centered
+    pub fn max_width(mut self, width: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MAX_WIDTH),
+            AttrValue::Size(width),
+        );
+        self
+    }
+
     /// Set text editor cursor style
     pub fn cursor_style(mut self, s: Style) -> Self {
         self.attr(
@@ -281,6 +753,16 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set the style patched on the line number of the cursor row, such as bold to emphasize the
+    /// current line number
+    pub fn cursor_gutter_style(mut self, s: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_CURSOR_GUTTER_STYLE),
+            AttrValue::Style(s),
+        );
+        self
+    }
+
     /// Set text editor style for selected line
     pub fn cursor_line_style(mut self, s: Style) -> Self {
         self.attr(
@@ -303,6 +785,108 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set whether typing an opening bracket or quote inserts its closer after the cursor.
+    /// Typing that closer types over it, while quotes typed right after a word are not closed
+    pub fn auto_close(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_AUTO_CLOSE),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set the pairs closed by `auto_close`, as a string made of each opener followed by its closer.
+    /// Default: `()[]{}""''`
+    pub fn auto_close_pairs(mut self, pairs: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_AUTO_CLOSE_PAIRS),
+            AttrValue::String(pairs.to_string()),
+        );
+        self
+    }
+
+    /// Set the char displayed in place of each char of the text, such as `*` for a password field.
+    /// The state still holds the real text. While masked, the word and bracket highlights are not
+    /// shown, and the copy and cut commands do nothing, so that the text isn't exposed
+    pub fn mask_char(mut self, mask: char) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MASK_CHAR),
+            AttrValue::String(mask.to_string()),
+        );
+        self
+    }
+
+    /// Set whether to highlight the occurrences of the word at the cursor with `style`
+    pub fn highlight_word(mut self, enabled: bool, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_HIGHLIGHT_WORD),
+            AttrValue::Flag(enabled),
+        );
+        self.attr(
+            Attribute::Custom(TEXTAREA_HIGHLIGHT_WORD_STYLE),
+            AttrValue::Style(style),
+        );
+        self
+    }
+
+    /// Set whether to highlight the bracket at the cursor, or before it, along with its matching one
+    pub fn match_brackets(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MATCH_BRACKETS),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set the style of the matching brackets. Default: bold and underlined
+    pub fn match_bracket_style(mut self, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MATCH_BRACKET_STYLE),
+            AttrValue::Style(style),
+        );
+        self
+    }
+
+    /// Set the style of the bracket at the cursor when it has no match. Default: no style
+    pub fn unmatched_bracket_style(mut self, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_UNMATCHED_BRACKET_STYLE),
+            AttrValue::Style(style),
+        );
+        self
+    }
+
+    /// Set the amount of rows of the footer bar; a footer taller than one row wraps its text. Default: 1
+    pub fn footer_height(mut self, height: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_FOOTER_HEIGHT),
+            AttrValue::Size(height),
+        );
+        self
+    }
+
+    /// Set the margin around the content of the textarea, on all sides.
+    /// Default: 1 if the textarea has borders, 0 otherwise
+    pub fn layout_margin(mut self, margin: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_LAYOUT_MARGIN),
+            AttrValue::Size(margin),
+        );
+        self
+    }
+
+    /// Set the horizontal and the vertical margins around the content of the textarea
+    pub fn layout_margins(mut self, horizontal: u16, vertical: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_LAYOUT_MARGIN),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::U16(horizontal),
+                PropValue::U16(vertical),
+            ))),
+        );
+        self
+    }
+
     /// Set text editor style for line numbers
     pub fn line_number_style(mut self, s: Style) -> Self {
         self.attr(
@@ -312,6 +896,18 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set the placeholder text and its style, displayed while the textarea is empty
+    pub fn placeholder(mut self, text: &str, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_PLACEHOLDER),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(text.to_string()),
+                PropValue::Style(style),
+            ))),
+        );
+        self
+    }
+
     /// Set status bar fmt and style for the status bar
     /// Default: no status bar is displayed
     pub fn status_bar(mut self, fmt: &str, style: Style) -> Self {
@@ -325,6 +921,45 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set the amount of rows of the status bar; a status taller than one row wraps its text. Default: 1
+    pub fn status_height(mut self, height: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_STATUS_HEIGHT),
+            AttrValue::Size(height),
+        );
+        self
+    }
+
+    /// Set the minimum amount of columns of the line numbers, so that the gutter keeps its width
+    /// while the amount of lines grows. Wider numbers still expand the gutter
+    pub fn line_number_width(mut self, width: u16) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_LINE_NUMBER_WIDTH),
+            AttrValue::Size(width),
+        );
+        self
+    }
+
+    /// Set whether the line numbers are displayed as the distance from the cursor row, which
+    /// displays its own line number. Requires the line numbers to be shown with `line_number_style`
+    pub fn relative_line_numbers(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_RELATIVE_LINE_NUMBERS),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set whether to show the line numbers, regardless of `line_number_style`.
+    /// If unset, the line numbers are shown once their style is set
+    pub fn show_line_numbers(mut self, show: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SHOW_LINE_NUMBERS),
+            AttrValue::Flag(show),
+        );
+        self
+    }
+
     /// Set text style for editor
     pub fn style(mut self, s: Style) -> Self {
         self.attr(Attribute::Style, AttrValue::Style(s));
@@ -349,6 +984,16 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set whether typing a tab indents the line when the cursor is in its indentation, or the
+    /// selected lines, instead of inserting a tab
+    pub fn smart_tab(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SMART_TAB),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
     /// Set single-line behavior
     pub fn single_line(mut self, single_line: bool) -> Self {
         self.attr(
@@ -358,342 +1003,4299 @@ impl<'a> TextArea<'a> {
         self
     }
 
-    #[cfg(feature = "search")]
-    /// Set search style
-    pub fn search_style(mut self, s: Style) -> Self {
+    /// Draw vertical rulers with `style` at the display `columns`, starting from 1, such as 81 to
+    /// mark the text past 80 columns
+    pub fn rulers(mut self, columns: &[u16], style: Style) -> Self {
         self.attr(
-            Attribute::Custom(TEXTAREA_SEARCH_STYLE),
-            AttrValue::Style(s),
+            Attribute::Custom(TEXTAREA_RULER_COLUMN),
+            AttrValue::Payload(PropPayload::Vec(
+                columns.iter().map(|col| PropValue::U16(*col)).collect(),
+            )),
+        );
+        self.attr(
+            Attribute::Custom(TEXTAREA_RULER_STYLE),
+            AttrValue::Style(style),
         );
         self
     }
 
-    // -- private
-    fn get_block(&self) -> Option<Block<'a>> {
-        let mut block = Block::default();
-        if let Some(AttrValue::Title((title, alignment))) = self.query(Attribute::Title) {
-            block = block.title(title).title_alignment(alignment);
-        }
-        if let Some(AttrValue::Borders(borders)) = self.query(Attribute::Borders) {
-            let inactive_style = self
-                .query(Attribute::FocusStyle)
-                .unwrap_or_else(|| AttrValue::Style(Style::default()))
-                .unwrap_style();
-            let focus = self
-                .props
-                .get_or(Attribute::Focus, AttrValue::Flag(false))
-                .unwrap_flag();
+    /// Set whether, in single line mode, a newline submits the text instead of being ignored
+    pub fn enter_submits(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_ENTER_SUBMITS),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
 
-            return Some(
-                block
-                    .border_style(match focus {
-                        true => borders.style(),
-                        false => inactive_style,
-                    })
-                    .border_type(borders.modifiers)
-                    .borders(borders.sides),
-            );
-        }
+    /// Set read-only mode, which rejects all the commands changing the text
+    pub fn read_only(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_READONLY),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
 
-        None
+    /// Set the commands to ignore, by their `TEXTAREA_CMD_*` id
+    pub fn disabled_cmds(mut self, cmds: &[&str]) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_DISABLED_CMDS),
+            AttrValue::Payload(PropPayload::Vec(
+                cmds.iter()
+                    .map(|cmd| PropValue::Str(cmd.to_string()))
+                    .collect(),
+            )),
+        );
+        self
     }
 
-    #[cfg(feature = "clipboard")]
-    fn paste(&mut self) {
-        // get content from context
-        if let Ok(Ok(yank)) = ClipboardContext::new().map(|mut ctx| ctx.get_contents()) {
-            // TODO: It's desired to set and paste yanked text, but pasting new lines as part of the yanked
-            // text is currently not supported by the textarea widget. Therefor, each line is inserted
-            // separately. The disadvantage of this workaround is, that each newly inserted line is a
-            // separate entry in the history and therefor a separate undo step.
-            if self.single_line {
-                self.widget.insert_str(yank);
-            } else {
-                for line in yank.lines() {
-                    self.widget.insert_str(line);
-                    self.widget.insert_newline();
-                }
-            }
-        }
+    /// Set where the cursor is moved when the textarea gains focus
+    pub fn focus_cursor_behavior(mut self, behavior: FocusCursorBehavior) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_FOCUS_CURSOR_BEHAVIOR),
+            AttrValue::String(behavior.as_str().to_string()),
+        );
+        self
     }
-}
 
-impl<'a> MockComponent for TextArea<'a> {
-    fn view(&mut self, frame: &mut Frame, area: Rect) {
-        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
-            // set block
-            if let Some(block) = self.get_block() {
-                self.widget.set_block(block);
-            }
-            let margin = if self.get_block().is_some() { 1 } else { 0 };
-            // make chunks
-            let chunks = Layout::default()
-                .direction(LayoutDirection::Vertical)
-                .margin(margin)
-                .constraints(
-                    [
-                        Constraint::Min(1),
-                        Constraint::Length(if self.status_fmt.is_some() { 1 } else { 0 }),
-                        Constraint::Length(if self.footer_fmt.is_some() { 1 } else { 0 }),
-                    ]
-                    .as_ref(),
-                )
-                .split(area);
+    /// Set the cursor style while the textarea is not focused, such as a dim cursor showing where
+    /// the focus will return. Default: hidden
+    pub fn unfocused_cursor_style(mut self, style: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_UNFOCUSED_CURSOR_STYLE),
+            AttrValue::Style(style),
+        );
+        self
+    }
 
-            // Remove cursor if not in focus
-            let focus = self
-                .props
-                .get_or(Attribute::Focus, AttrValue::Flag(false))
-                .unwrap_flag();
-            if !focus {
-                self.widget.set_cursor_style(Style::reset());
-            } else {
-                let style = self
-                    .props
-                    .get_or(
-                        Attribute::Custom(TEXTAREA_CURSOR_STYLE),
-                        AttrValue::Style(Style::default().add_modifier(TextModifiers::REVERSED)),
-                    )
-                    .unwrap_style();
-                self.widget.set_cursor_style(style);
-            }
+    /// Set the line ending which joins the lines of `text()`
+    pub fn line_ending(mut self, ending: LineEnding) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_LINE_ENDING),
+            AttrValue::String(ending.as_str().to_string()),
+        );
+        self
+    }
 
-            // render widget
-            frame.render_widget(&self.widget, chunks[0]);
-            if let Some(fmt) = self.status_fmt.as_ref() {
-                frame.render_widget(
-                    Paragraph::new(fmt.fmt(&self.widget)).style(fmt.style()),
-                    chunks[1],
-                );
-            }
-            if let Some(fmt) = self.footer_fmt.as_ref() {
-                frame.render_widget(
-                    Paragraph::new(fmt.fmt(&self.widget)).style(fmt.style()),
-                    chunks[2],
-                );
+    /// Set whether `text()` drops a trailing empty line, so the text doesn't end with a line ending.
+    /// The lines in the textarea are not changed
+    pub fn trim_trailing_newline(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_TRIM_TRAILING_NEWLINE),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set whether the lines longer than the textarea are wrapped on the next rows, instead of scrolling horizontally
+    pub fn line_wrap(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_LINE_WRAP),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set whether the rows continuing a wrapped line are indented as its leading whitespace.
+    /// Only applies if line wrap is enabled
+    pub fn wrap_indent(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_WRAP_INDENT),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set the name of the edited file, displayed by the `{FILENAME}` key of the status and footer format
+    pub fn filename(mut self, name: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_FILENAME),
+            AttrValue::String(name.to_string()),
+        );
+        self
+    }
+
+    /// Set the marker displayed by the `{MODIFIED}` key of the status and footer format
+    /// while the text is modified. Default: `[+]`
+    pub fn modified_marker(mut self, marker: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_MODIFIED_MARKER),
+            AttrValue::String(marker.to_string()),
+        );
+        self
+    }
+
+    /// Set the prefix which comments a line, such as `// ` or `# `, used by `TEXTAREA_CMD_TOGGLE_COMMENT`
+    pub fn comment_prefix(mut self, prefix: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_COMMENT_PREFIX),
+            AttrValue::String(prefix.to_string()),
+        );
+        self
+    }
+
+    /// Set the pair which opens and closes a block comment, such as `/* ` and ` */`,
+    /// used by `TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT`
+    pub fn block_comment(mut self, open: &str, close: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_BLOCK_COMMENT),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(open.to_string()),
+                PropValue::Str(close.to_string()),
+            ))),
+        );
+        self
+    }
+
+    /// Set the separator inserted between joined lines by `TEXTAREA_CMD_JOIN_LINES`. Default: a space
+    pub fn join_separator(mut self, separator: &str) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_JOIN_SEPARATOR),
+            AttrValue::String(separator.to_string()),
+        );
+        self
+    }
+
+    /// Set whether to draw vertical guides at the tab stops in the indentation of the lines
+    pub fn indent_guides(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_INDENT_GUIDES),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set the style of the indent guides. Default: dimmed
+    pub fn indent_guide_style(mut self, s: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_INDENT_GUIDE_STYLE),
+            AttrValue::Style(s),
+        );
+        self
+    }
+
+    /// Set whether to draw the leading and trailing spaces and the tabs as visible glyphs
+    pub fn show_whitespace(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SHOW_WHITESPACE),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    /// Set the glyph drawn on the first column of the tabs, such as `▸`, even if `show_whitespace` is disabled
+    pub fn tab_char(mut self, glyph: char) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_TAB_CHAR),
+            AttrValue::String(glyph.to_string()),
+        );
+        self
+    }
+
+    /// Set the glyphs drawn in place of spaces and tabs when `show_whitespace` is enabled.
+    /// Default: `·` and `→`
+    pub fn whitespace_chars(mut self, space: char, tab: char) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_WHITESPACE_CHARS),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(space.to_string()),
+                PropValue::Str(tab.to_string()),
+            ))),
+        );
+        self
+    }
+
+    #[cfg(feature = "search")]
+    /// Set search style
+    pub fn search_style(mut self, s: Style) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_STYLE),
+            AttrValue::Style(s),
+        );
+        self
+    }
+
+    #[cfg(feature = "search")]
+    /// Set whether the view is scrolled to put the line of the match found by a search in its middle
+    pub fn search_center(mut self, enabled: bool) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_CENTER),
+            AttrValue::Flag(enabled),
+        );
+        self
+    }
+
+    #[cfg(feature = "search")]
+    /// Set all the search options at once, updating the current search pattern
+    pub fn search_options(mut self, options: SearchOptions) -> Self {
+        self.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_OPTIONS),
+            Self::search_options_value(options),
+        );
+        self
+    }
+
+    /// Get the amount of visual rows the logical line at `row` spans, when wrapped at `width` columns.
+    /// `width` is the width of the text only, without the line number gutter.
+    /// Returns `0` if `row` is out of range
+    pub fn visual_rows_for_line(&self, row: usize, width: u16) -> usize {
+        let tab_len = self.widget.tab_length();
+        self.widget
+            .lines()
+            .get(row)
+            .map(|line| {
+                let indent = match self.wraps_indent() {
+                    true => wrap::break_indent(line, width as usize, tab_len),
+                    false => 0,
+                };
+                wrap::wrap_line(line, width as usize, indent, tab_len).len()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Insert `s` at the cursor as a single history step, breaking the line on each `\n` or `\r\n`.
+    /// In single line mode only the text before the first line break is inserted
+    pub fn insert_str(&mut self, s: &str) {
+        self.edit_near_cursor(|this| this.insert_text(s));
+    }
+
+    /// Append `text` at the end of the text as a single history step, breaking the line on each `\n`.
+    /// If the cursor was at the end of the text it follows the appended text, otherwise it's left in place
+    pub fn append_text(&mut self, text: &str) {
+        let len = self.widget.lines().len();
+        self.record(len - 1..len, false, |this| {
+            let cursor = this.widget.cursor();
+            let end = this.end_position();
+            this.jump(end);
+            this.insert_text(text);
+            if cursor != end {
+                this.jump(cursor);
             }
+        });
+    }
+
+    /// Append `line` as a new line at the end of the text, such as for log tailing.
+    /// If the text is empty, `line` replaces the empty line.
+    /// The cursor follows as for `append_text`
+    pub fn append_line(&mut self, line: &str) {
+        match self.widget.is_empty() {
+            true => self.append_text(line),
+            false => self.append_text(&format!("\n{line}")),
         }
     }
 
-    fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+    /// Set the text of the yank buffer, which is pasted by `TEXTAREA_CMD_PASTE` when the system clipboard
+    /// is not available
+    pub fn set_yank(&mut self, text: String) {
+        self.widget.set_yank_text(text);
     }
 
-    fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value.clone());
-        match (attr, value) {
-            (Attribute::Custom(TEXTAREA_CURSOR_STYLE), AttrValue::Style(s)) => {
-                self.widget.set_cursor_style(s);
-            }
-            (Attribute::Custom(TEXTAREA_CURSOR_LINE_STYLE), AttrValue::Style(s)) => {
-                self.widget.set_cursor_line_style(s);
-            }
-            (
-                Attribute::Custom(TEXTAREA_FOOTER_FMT),
-                AttrValue::Payload(PropPayload::Tup2((
-                    PropValue::Str(fmt),
-                    PropValue::Style(style),
-                ))),
-            ) => {
-                self.footer_fmt = Some(LineFmt::new(&fmt, style));
-            }
-            (
-                Attribute::Custom(TEXTAREA_MAX_HISTORY),
-                AttrValue::Payload(PropPayload::One(PropValue::Usize(max))),
-            ) => {
-                self.widget.set_max_histories(max);
-            }
-            (
-                Attribute::Custom(TEXTAREA_STATUS_FMT),
-                AttrValue::Payload(PropPayload::Tup2((
-                    PropValue::Str(fmt),
-                    PropValue::Style(style),
-                ))),
-            ) => {
-                self.status_fmt = Some(LineFmt::new(&fmt, style));
-            }
-            (Attribute::Custom(TEXTAREA_LINE_NUMBER_STYLE), AttrValue::Style(s)) => {
-                self.widget.set_line_number_style(s);
-            }
-            (Attribute::Custom(TEXTAREA_TAB_SIZE), AttrValue::Size(size)) => {
-                self.widget.set_tab_length(size as u8);
-            }
-            (Attribute::Custom(TEXTAREA_HARD_TAB), AttrValue::Flag(enabled)) => {
-                self.widget.set_hard_tab_indent(enabled);
-            }
-            (Attribute::Custom(TEXTAREA_SINGLE_LINE), AttrValue::Flag(single_line)) => {
-                self.single_line = single_line;
-            }
-            #[cfg(feature = "search")]
-            (Attribute::Custom(TEXTAREA_SEARCH_PATTERN), AttrValue::String(pattern)) => {
-                let _ = self.widget.set_search_pattern(pattern);
-            }
-            #[cfg(feature = "search")]
-            (Attribute::Custom(TEXTAREA_SEARCH_STYLE), AttrValue::Style(s)) => {
-                self.widget.set_search_style(s);
-            }
-            (Attribute::Style, AttrValue::Style(s)) => {
-                self.widget.set_style(s);
-            }
-            (_, _) => {
-                if let Some(block) = self.get_block() {
-                    self.widget.set_block(block);
-                }
-            }
+    /// Paste `text` at the cursor, replacing the selection, as a single history step.
+    /// Unlike `TEXTAREA_CMD_PASTE`, the system clipboard is not read
+    pub fn paste_str(&mut self, text: &str) {
+        self.edit_near_cursor(|this| this.insert_text(text));
+    }
+
+    /// Replace the text of the line at `idx` with `text` as a single history step, keeping the cursor
+    /// on its position, clamped to the line. Only the text before the first line break is set,
+    /// and nothing is done if `idx` is out of range
+    pub fn set_line(&mut self, idx: usize, text: &str) {
+        if idx >= self.widget.lines().len() {
+            return;
         }
+        let line = text.lines().next().unwrap_or_default().to_string();
+        self.record(idx..idx + 1, false, |this| {
+            let cursor = this.widget.cursor();
+            this.replace_lines(idx, 1, &[line]);
+            this.jump(cursor);
+        });
     }
 
-    fn state(&self) -> State {
-        State::Vec(
-            self.widget
-                .lines()
-                .iter()
-                .map(|x| StateValue::String(x.to_string()))
-                .collect(),
-        )
+    /// Empty the text, leaving a single empty line, as a single history step which can be undone.
+    /// Unlike `set_lines`, the history is kept
+    pub fn clear(&mut self) {
+        self.edit(|this| {
+            let len = this.widget.lines().len();
+            this.replace_lines(0, len, &[String::new()]);
+            this.jump((0, 0));
+        });
     }
 
-    fn perform(&mut self, cmd: Cmd) -> CmdResult {
-        match cmd {
-            Cmd::Cancel => {
-                self.widget.delete_next_char();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_DEL_LINE_BY_END) => {
-                self.widget.delete_line_by_end();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_DEL_LINE_BY_HEAD) => {
-                self.widget.delete_line_by_head();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_DEL_NEXT_WORD) => {
-                self.widget.delete_next_word();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_DEL_WORD) => {
-                self.widget.delete_word();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_PARAGRAPH_BACK) => {
-                self.widget.move_cursor(CursorMove::ParagraphBack);
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_PARAGRAPH_FORWARD) => {
-                self.widget.move_cursor(CursorMove::ParagraphForward);
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_WORD_BACK) => {
-                self.widget.move_cursor(CursorMove::WordBack);
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_WORD_FORWARD) => {
-                self.widget.move_cursor(CursorMove::WordForward);
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_BOTTOM) => {
-                if !self.single_line {
-                    self.widget.move_cursor(CursorMove::Bottom);
-                }
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_MOVE_TOP) => {
-                if !self.single_line {
-                    self.widget.move_cursor(CursorMove::Top);
-                }
-                CmdResult::None
-            }
-            #[cfg(feature = "clipboard")]
-            Cmd::Custom(TEXTAREA_CMD_PASTE) => {
-                self.paste();
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_REDO) => {
-                self.widget.redo();
-                CmdResult::None
-            }
-            #[cfg(feature = "search")]
-            Cmd::Custom(TEXTAREA_CMD_SEARCH_BACK) => {
-                self.widget.search_back(true);
-                CmdResult::None
+    /// Replace the text in the textarea with `lines`, moving the cursor to the top.
+    /// The history is cleared, while the properties are kept.
+    /// The `on_change` callback is not called, since the text is replaced rather than edited
+    pub fn set_lines(&mut self, lines: Vec<String>) {
+        self.widget = TextAreaWidget::new(lines);
+        self.widget.set_max_histories(0);
+        self.history.clear();
+        self.reset_modified();
+        self.viewport = Viewport::default();
+        self.drag_anchor = None;
+        self.auto_closed.clear();
+        self.update_widget();
+    }
+
+    /// Take a snapshot of the text and of the cursor position, such as to persist the editing session
+    pub fn snapshot(&self) -> TextAreaSnapshot {
+        TextAreaSnapshot {
+            lines: self.widget.lines().to_vec(),
+            cursor: self.widget.cursor(),
+        }
+    }
+
+    /// Replace the text with the one of `snap`, and move the cursor to its position, clamped to the text.
+    /// As with `set_lines`, the history is cleared and the properties are kept
+    pub fn restore(&mut self, snap: TextAreaSnapshot) {
+        self.set_lines(snap.lines);
+        self.jump(snap.cursor);
+    }
+
+    /// Register a custom `{KEY}` for the status and footer format, formatted by `f`.
+    /// Builtin keys can't be overridden.
+    /// `f` is called on every render, so it should be cheap
+    pub fn register_fmt_key(&mut self, key: &str, f: CustomFmtCallback) {
+        self.fmt_keys.insert(key.to_string(), f);
+        // make the formats again, so that they resolve the new key
+        for attr in [TEXTAREA_STATUS_FMT, TEXTAREA_FOOTER_FMT] {
+            if let Some(value) = self.props.get(Attribute::Custom(attr)) {
+                self.attr(Attribute::Custom(attr), value);
             }
-            #[cfg(feature = "search")]
-            Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD) => {
-                self.widget.search_forward(true);
-                CmdResult::None
-            }
-            Cmd::Custom(TEXTAREA_CMD_UNDO) => {
-                self.widget.undo();
-                CmdResult::None
+        }
+    }
+
+    /// Set the callback which styles the lines, such as to highlight syntax.
+    /// The callback gets the row and the text of each rendered line, and returns the styles of its
+    /// char ranges, given as char indices rather than byte offsets; the cursor, search, selection
+    /// and cursor line styles are patched over them
+    pub fn set_line_styler(&mut self, f: LineStyler) {
+        self.line_styler = Some(f);
+    }
+
+    /// Set the styles painted on the whole rows of the lines, such as for added and removed lines of a diff.
+    /// Styles are given by line index, replacing the previous ones; lines out of range are ignored.
+    /// The cursor line style and the styles of the text are patched over them.
+    /// Indexes are not shifted by the edits to the text
+    pub fn set_line_highlights(&mut self, highlights: Vec<(usize, Style)>) {
+        self.line_highlights = highlights.into_iter().collect();
+    }
+
+    /// Set the callback formatting the time displayed by the `{TIME}` key of the status and footer
+    /// format, such as the local time with the date and time crate used by the application.
+    /// Without a callback the key is empty, since the local time can't be told without such a crate
+    pub fn set_time_fmt(&mut self, f: TimeFmtCallback) {
+        self.time_fmt = Some(f);
+    }
+
+    /// Get the first row and display column shown by the textarea, as of the last render
+    pub fn viewport_offset(&self) -> (usize, usize) {
+        (self.viewport.row, self.viewport.col)
+    }
+
+    /// Scroll the textarea to show `row` and display column `col` at its top-left corner, such as
+    /// to scroll two textareas together. The cursor doesn't move, and is brought back into view
+    /// by the next command. The column is ignored while lines are wrapped
+    pub fn set_viewport_offset(&mut self, row: usize, col: usize) {
+        self.viewport
+            .set_row(row.min(self.widget.lines().len() - 1));
+        self.viewport.col = col;
+        self.follow_cursor = false;
+    }
+
+    /// Mark the content as invalid or valid, such as after validating a form field.
+    /// While invalid and focused, the borders are drawn with the invalid style
+    pub fn mark_invalid(&mut self, invalid: bool) {
+        self.attr(
+            Attribute::Custom(TEXTAREA_INVALID),
+            AttrValue::Flag(invalid),
+        );
+    }
+
+    /// Get whether the content is marked as invalid
+    pub fn is_invalid(&self) -> bool {
+        self.props
+            .get_or(Attribute::Custom(TEXTAREA_INVALID), AttrValue::Flag(false))
+            .unwrap_flag()
+    }
+
+    /// Get the height the textarea needs to display all its text when it's `width` columns wide,
+    /// counting the wrapped rows, the borders, the margins and the status and footer bars.
+    /// The rows of text are clamped between `min_visible_lines` and `max_visible_lines`.
+    /// Use it to size the layout constraint of an auto-growing textarea
+    pub fn desired_height(&self, width: u16) -> u16 {
+        let (horizontal_margin, vertical_margin) = self.layout_margins_size();
+        // `Rect::new` would shrink the width to keep the area in `u16`; only the borders height matters
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: width.saturating_sub(horizontal_margin.saturating_mul(2)),
+            height: 8,
+        };
+        let area = self.center_max_width(area);
+        let inner = self.get_block().map_or(area, |block| block.inner(area));
+        let rows = Renderer::new(&self.widget)
+            .line_wrap(self.wraps_lines())
+            .wrap_indent(self.wraps_indent())
+            .line_number_width(self.line_number_columns())
+            .text_rows(inner.width)
+            .min(self.limit(TEXTAREA_MAX_VISIBLE_LINES))
+            .max(
+                self.props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_MIN_VISIBLE_LINES),
+                        AttrValue::Length(0),
+                    )
+                    .unwrap_length(),
+            );
+        let bars = self
+            .status_fmt
+            .as_ref()
+            .map_or(0, |_| self.bar_height(TEXTAREA_STATUS_HEIGHT))
+            + self
+                .footer_fmt
+                .as_ref()
+                .map_or(0, |_| self.bar_height(TEXTAREA_FOOTER_HEIGHT));
+        (rows.min(u16::MAX as usize) as u16)
+            .saturating_add(area.height - inner.height)
+            .saturating_add(vertical_margin.saturating_mul(2))
+            .saturating_add(bars)
+    }
+
+    /// Whether `TEXTAREA_CMD_UNDO` has an edit to revert.
+    /// Always `false` if the history is disabled with `max_histories(0)`
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Whether `TEXTAREA_CMD_REDO` has an undone edit to apply again
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Get the amount of lines in the textarea, which is at least 1
+    pub fn line_count(&self) -> usize {
+        self.widget.lines().len()
+    }
+
+    /// Get the text of the line at `idx`, or `None` if it's out of range.
+    /// Unlike `state()`, the lines are not cloned
+    pub fn line(&self, idx: usize) -> Option<&str> {
+        self.widget.lines().get(idx).map(String::as_str)
+    }
+
+    /// Get the cursor position as `(row, column)`
+    pub fn cursor(&self) -> (usize, usize) {
+        self.widget.cursor()
+    }
+
+    #[cfg(feature = "search")]
+    /// Get the search matches as `(current, total)`, where `current` is the position of the match
+    /// at the cursor, or of the last one before it, starting from 1.
+    /// Returns `(0, 0)` if there's no search pattern
+    pub fn search_matches(&self) -> (usize, usize) {
+        self.widget
+            .search_pattern()
+            .map(|pattern| {
+                search::count_matches(pattern, self.widget.lines(), self.widget.cursor())
+            })
+            .unwrap_or((0, 0))
+    }
+
+    /// Get the selected text, with lines joined by `\n`.
+    /// Returns `None` if nothing is selected
+    pub fn selected_text(&self) -> Option<String> {
+        let ((start_row, start_col), (end_row, end_col)) = self.widget.selection_range()?;
+        let lines = self.widget.lines();
+        let text = (start_row..=end_row)
+            .map(|row| {
+                let start = if row == start_row { start_col } else { 0 };
+                let line = lines[row].chars().skip(start);
+                match row == end_row {
+                    true => line.take(end_col.saturating_sub(start)).collect(),
+                    false => line.collect::<String>(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Get whether the textarea acts as a single line input, set with `TEXTAREA_SINGLE_LINE`
+    pub fn is_single_line(&self) -> bool {
+        self.single_line
+    }
+
+    /// Get the text yanked to the register `name`, if any. The last yanked text is also in the
+    /// unnamed register, `"`
+    pub fn register(&self, name: char) -> Option<&str> {
+        self.registers.get(&name).map(String::as_str)
+    }
+
+    /// Get the text in the textarea, with lines joined by the line ending set with `TEXTAREA_LINE_ENDING`,
+    /// which is `\n` by default.
+    /// If `TEXTAREA_TRIM_TRAILING_NEWLINE` is set, a trailing empty line is left out
+    pub fn text(&self) -> String {
+        let ending = self.props.get_or(
+            Attribute::Custom(TEXTAREA_LINE_ENDING),
+            AttrValue::String(LineEnding::default().as_str().to_string()),
+        );
+        let trim = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_TRIM_TRAILING_NEWLINE),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        let lines = match self.widget.lines() {
+            [lines @ .., last] if trim && !lines.is_empty() && last.is_empty() => lines,
+            lines => lines,
+        };
+        lines.join(&ending.unwrap_string())
+    }
+
+    /// Get whether the text has changed since the textarea was created, or since the last call
+    /// to `set_lines` or `reset_modified`.
+    /// Undoing the changes makes the text not modified anymore
+    pub fn is_modified(&self) -> bool {
+        self.history.revision() != self.saved
+    }
+
+    /// Mark the current text as not modified, such as after it has been saved
+    pub fn reset_modified(&mut self) {
+        self.saved = self.history.revision();
+    }
+
+    // -- private
+
+    fn get_block(&self) -> Option<Block<'a>> {
+        let mut block = Block::default();
+        if let Some(AttrValue::Title((title, alignment))) = self.query(Attribute::Title) {
+            block = block.title(title).title_alignment(alignment);
+        }
+        if let Some(AttrValue::Borders(borders)) = self.query(Attribute::Borders) {
+            let inactive_style = self
+                .query(Attribute::FocusStyle)
+                .unwrap_or_else(|| AttrValue::Style(Style::default()))
+                .unwrap_style();
+            let focus = self
+                .props
+                .get_or(Attribute::Focus, AttrValue::Flag(false))
+                .unwrap_flag();
+
+            let invalid_style = self
+                .props
+                .get_or(
+                    Attribute::Custom(TEXTAREA_INVALID_STYLE),
+                    AttrValue::Style(Style::default().fg(Color::Red)),
+                )
+                .unwrap_style();
+
+            return Some(
+                block
+                    .border_style(match (focus, self.is_invalid()) {
+                        (true, true) => invalid_style,
+                        (true, false) => borders.style(),
+                        (false, _) => inactive_style,
+                    })
+                    .border_type(borders.modifiers)
+                    .borders(borders.sides),
+            );
+        }
+
+        None
+    }
+
+    /// Apply `f` to the textarea, recording the changes it makes to the text as a single history step
+    fn edit<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.record(0..self.widget.lines().len(), false, f);
+    }
+
+    /// Apply `f`, which only changes the lines around the cursor or the selection, such as typing
+    /// or deleting a char, recording its changes as a single history step
+    fn edit_near_cursor<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.record(self.rows_near_cursor(), false, f);
+    }
+
+    /// Get the rows of the cursor and of the selection, along with the row before and the row after
+    /// them, which may be joined to them by deleting a line break
+    fn rows_near_cursor(&self) -> Range<usize> {
+        let (row, _) = self.widget.cursor();
+        let (start, end) = self
+            .widget
+            .selection_range()
+            .map_or((row, row), |((start, _), (end, _))| {
+                (start.min(row), end.max(row))
+            });
+        start.saturating_sub(1)..(end + 2).min(self.widget.lines().len())
+    }
+
+    /// Apply `f` to the textarea, recording the changes it makes to the lines in `rows`, which are the
+    /// only ones it may change, as a history step; this way only those lines are copied and compared.
+    /// If `typing`, the step is merged into the previous one when it continues typing from there
+    fn record<F>(&mut self, rows: Range<usize>, typing: bool, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let len = self.widget.lines().len();
+        let before = self.widget.lines()[rows.clone()].to_vec();
+        let cursor = self.widget.cursor();
+        f(self);
+        let lines = self.widget.lines();
+        // the lines after `rows` are moved by the lines added or removed
+        let after = &lines[rows.start..rows.end + lines.len() - len];
+        if let Some(mut edit) = Edit::diff(&before, after, cursor, self.widget.cursor()) {
+            edit.row += rows.start;
+            let grown = Self::text_length(&edit.after) > Self::text_length(&edit.before);
+            let max_length = self.limit(TEXTAREA_MAX_LENGTH);
+            let new_len = self.widget.lines().len();
+            // the whole text is counted only if its length is limited
+            if (grown && max_length < usize::MAX && self.length() > max_length)
+                || (new_len > len && new_len > self.limit(TEXTAREA_MAX_LINES))
+            {
+                // revert edits exceeding the limits
+                self.replace_lines(edit.row, edit.after.len(), &edit.before);
+                self.jump(edit.cursor_before);
+            } else {
+                match typing {
+                    true => self.history.push_typing(edit),
+                    false => self.history.push(edit),
+                }
+                self.notify_change();
             }
-            Cmd::Delete => {
-                self.widget.delete_char();
-                CmdResult::None
+        }
+    }
+
+    /// Split the column of the scrollbar from the right of `area`, if the scrollbar is enabled and the
+    /// text doesn't fit the area. The scrollbar is as tall as the text inside the block, and it takes
+    /// the column of the right border if any
+    fn split_scrollbar(&self, area: Rect) -> (Rect, Option<Rect>) {
+        let enabled = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_SCROLLBAR),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        if !enabled || area.width < 2 {
+            return (area, None);
+        }
+        let right_border = matches!(
+            self.query(Attribute::Borders),
+            Some(AttrValue::Borders(borders)) if borders.sides.contains(BorderSides::RIGHT)
+        );
+        let text = match right_border {
+            true => area,
+            false => Rect {
+                width: area.width - 1,
+                ..area
+            },
+        };
+        let renderer = Renderer::new(&self.widget)
+            .line_wrap(self.wraps_lines())
+            .wrap_indent(self.wraps_indent())
+            .line_number_width(self.line_number_columns());
+        let inner = renderer.text_area(text);
+        match renderer.text_rows(inner.width) > inner.height as usize {
+            true => (
+                text,
+                Some(Rect::new(inner.right(), inner.y, 1, inner.height)),
+            ),
+            false => (area, None),
+        }
+    }
+
+    /// Render the scrollbar in `area`, with the thumb at the first visible row
+    fn render_scrollbar(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.widget.lines().len();
+        // the thumb reaches the bottom once the last line is visible at the bottom, or at the top
+        // if lines are wrapped
+        let positions = match self.wraps_lines() {
+            true => rows,
+            false => rows.saturating_sub(area.height as usize) + 1,
+        };
+        let style = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_SCROLLBAR_STYLE),
+                AttrValue::Style(Style::default()),
+            )
+            .unwrap_style();
+        let mut state = ScrollbarState::new(positions)
+            .viewport_content_length(area.height as usize)
+            .position(self.viewport.row);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(style),
+            area,
+            &mut state,
+        );
+    }
+
+    /// Get the part of `area` centered horizontally and not wider than `TEXTAREA_MAX_WIDTH`
+    fn center_max_width(&self, area: Rect) -> Rect {
+        match self.props.get(Attribute::Custom(TEXTAREA_MAX_WIDTH)) {
+            Some(AttrValue::Size(max)) if area.width > max => Rect {
+                x: area.x + (area.width - max) / 2,
+                width: max,
+                ..area
+            },
+            _ => area,
+        }
+    }
+
+    /// Get the horizontal and the vertical margins around the content
+    fn layout_margins_size(&self) -> (u16, u16) {
+        match self.props.get(Attribute::Custom(TEXTAREA_LAYOUT_MARGIN)) {
+            Some(AttrValue::Size(margin)) => (margin, margin),
+            Some(AttrValue::Payload(PropPayload::Tup2((
+                PropValue::U16(horizontal),
+                PropValue::U16(vertical),
+            )))) => (horizontal, vertical),
+            _ if self.get_block().is_some() => (1, 1),
+            _ => (0, 0),
+        }
+    }
+
+    /// Show the line numbers on the widget with the line number style, according to the show line
+    /// numbers property, or if the style is set when the property is unset
+    /// Apply the properties held by the widget, such as its styles, from the properties of the textarea
+    fn update_widget(&mut self) {
+        if let Some(AttrValue::Style(s)) = self.props.get(Attribute::Custom(TEXTAREA_CURSOR_STYLE))
+        {
+            self.widget.set_cursor_style(s);
+        }
+        if let Some(AttrValue::Style(s)) = self
+            .props
+            .get(Attribute::Custom(TEXTAREA_CURSOR_LINE_STYLE))
+        {
+            self.widget.set_cursor_line_style(s);
+        }
+        if let Some(AttrValue::Payload(PropPayload::Tup2((
+            PropValue::Str(text),
+            PropValue::Style(style),
+        )))) = self.props.get(Attribute::Custom(TEXTAREA_PLACEHOLDER))
+        {
+            self.widget.set_placeholder_text(text);
+            self.widget.set_placeholder_style(style);
+        }
+        self.update_line_numbers();
+        if let Some(AttrValue::String(mask)) = self.props.get(Attribute::Custom(TEXTAREA_MASK_CHAR))
+        {
+            match mask.chars().next() {
+                Some(mask) => self.widget.set_mask_char(mask),
+                None => self.widget.clear_mask_char(),
             }
-            Cmd::GoTo(Position::Begin) => {
-                self.widget.move_cursor(CursorMove::Head);
-                CmdResult::None
+        }
+        if let Some(AttrValue::Size(size)) = self.props.get(Attribute::Custom(TEXTAREA_TAB_SIZE)) {
+            self.widget.set_tab_length(size as u8);
+        }
+        if let Some(AttrValue::Flag(enabled)) = self.props.get(Attribute::Custom(TEXTAREA_HARD_TAB))
+        {
+            self.widget.set_hard_tab_indent(enabled);
+        }
+        #[cfg(feature = "search")]
+        {
+            self.update_search_pattern();
+            if let Some(AttrValue::Style(s)) =
+                self.props.get(Attribute::Custom(TEXTAREA_SEARCH_STYLE))
+            {
+                self.widget.set_search_style(s);
             }
-            Cmd::GoTo(Position::End) => {
-                self.widget.move_cursor(CursorMove::End);
-                CmdResult::None
+        }
+        if let Some(AttrValue::Style(s)) = self.props.get(Attribute::Style) {
+            self.widget.set_style(s);
+        }
+        if let Some(block) = self.get_block() {
+            self.widget.set_block(block);
+        }
+    }
+
+    fn update_line_numbers(&mut self) {
+        let style = self
+            .props
+            .get(Attribute::Custom(TEXTAREA_LINE_NUMBER_STYLE))
+            .map(|x| x.unwrap_style());
+        match (
+            self.props
+                .get(Attribute::Custom(TEXTAREA_SHOW_LINE_NUMBERS)),
+            style,
+        ) {
+            (Some(AttrValue::Flag(false)), _) | (None, None) => self.widget.remove_line_number(),
+            (_, style) => self.widget.set_line_number_style(style.unwrap_or_default()),
+        }
+    }
+
+    /// Whether the text is displayed masked, so it mustn't be exposed
+    fn is_masked(&self) -> bool {
+        self.widget.mask_char().is_some()
+    }
+
+    /// Get the brackets to highlight with their style: the bracket at the cursor and its match,
+    /// or the unmatched bracket at the cursor
+    fn bracket_highlights(&self) -> Vec<((usize, usize), Style)> {
+        let enabled = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_MATCH_BRACKETS),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        let enabled = enabled && !self.is_masked();
+        let lines = self.widget.lines();
+        let Some(bracket) = bracket::bracket_at(lines, self.widget.cursor()).filter(|_| enabled)
+        else {
+            return Vec::new();
+        };
+        match bracket::matching_bracket(lines, bracket) {
+            Some(matching) => {
+                let style = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_MATCH_BRACKET_STYLE),
+                        AttrValue::Style(
+                            Style::default()
+                                .add_modifier(TextModifiers::BOLD | TextModifiers::UNDERLINED),
+                        ),
+                    )
+                    .unwrap_style();
+                vec![(bracket, style), (matching, style)]
             }
-            Cmd::Move(Direction::Down) => {
-                if !self.single_line {
-                    self.widget.move_cursor(CursorMove::Down);
+            None => self
+                .props
+                .get(Attribute::Custom(TEXTAREA_UNMATCHED_BRACKET_STYLE))
+                .map(|style| vec![(bracket, style.unwrap_style())])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Get the chars to highlight as the whole-word occurrences of the word at the cursor, in the rows
+    /// which may be visible in an area `height` rows tall
+    fn word_highlights(&self, height: usize) -> Vec<((usize, usize), Style)> {
+        let enabled = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_HIGHLIGHT_WORD),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag()
+            && !self.is_masked();
+        let lines = self.widget.lines();
+        let (row, col) = self.widget.cursor();
+        let Some((start, end)) = word::word_at(&lines[row], col).filter(|_| enabled) else {
+            return Vec::new();
+        };
+        let style = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_HIGHLIGHT_WORD_STYLE),
+                AttrValue::Style(Style::default().add_modifier(TextModifiers::UNDERLINED)),
+            )
+            .unwrap_style();
+        let word: String = lines[row].chars().skip(start).take(end - start).collect();
+        let len = end - start;
+        // the viewport contains the cursor, unless scrolled by the mouse
+        let first = self.viewport.row.min(row).saturating_sub(height);
+        let last = (self.viewport.row.max(row) + height).min(lines.len() - 1);
+        (first..=last)
+            .flat_map(|row| {
+                word::word_occurrences(&lines[row], &word)
+                    .into_iter()
+                    .flat_map(move |col| (col..col + len).map(move |col| ((row, col), style)))
+            })
+            .collect()
+    }
+
+    /// Get the display columns of the rulers, starting from 0
+    fn ruler_columns(&self) -> Vec<usize> {
+        let columns = match self.props.get(Attribute::Custom(TEXTAREA_RULER_COLUMN)) {
+            Some(AttrValue::Size(col)) => vec![col],
+            Some(AttrValue::Payload(PropPayload::Vec(cols))) => cols
+                .into_iter()
+                .filter_map(|col| match col {
+                    PropValue::U16(col) => Some(col),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        columns
+            .into_iter()
+            .filter_map(|col| (col as usize).checked_sub(1))
+            .collect()
+    }
+
+    /// Get the minimum amount of columns of the line numbers
+    fn line_number_columns(&self) -> usize {
+        self.props
+            .get_or(
+                Attribute::Custom(TEXTAREA_LINE_NUMBER_WIDTH),
+                AttrValue::Size(0),
+            )
+            .unwrap_size() as usize
+    }
+
+    /// Get the amount of rows of the status or footer bar set by the `Size` property `attr`
+    fn bar_height(&self, attr: &'static str) -> u16 {
+        self.props
+            .get_or(Attribute::Custom(attr), AttrValue::Size(1))
+            .unwrap_size()
+    }
+
+    /// Get the amount of characters in the textarea, counting line breaks as one character
+    fn length(&self) -> usize {
+        Self::text_length(self.widget.lines()) - 1
+    }
+
+    /// Get the amount of characters in `lines`, along with a line break after each of them
+    fn text_length(lines: &[String]) -> usize {
+        lines.iter().map(|x| x.chars().count() + 1).sum()
+    }
+
+    /// Get the limit set by the `Length` property `attr`
+    fn limit(&self, attr: &'static str) -> usize {
+        match self.props.get(Attribute::Custom(attr)) {
+            Some(AttrValue::Length(max)) => max,
+            _ => usize::MAX,
+        }
+    }
+
+    /// Get the style of the indent guides, if they are drawn
+    fn indent_guides_style(&self) -> Option<Style> {
+        let enabled = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_INDENT_GUIDES),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        enabled.then(|| {
+            self.props
+                .get_or(
+                    Attribute::Custom(TEXTAREA_INDENT_GUIDE_STYLE),
+                    AttrValue::Style(Style::default().add_modifier(TextModifiers::DIM)),
+                )
+                .unwrap_style()
+        })
+    }
+
+    /// Get the glyph to draw on the first column of the tabs, if set
+    fn tab_glyph(&self) -> Option<char> {
+        self.props
+            .get(Attribute::Custom(TEXTAREA_TAB_CHAR))
+            .and_then(|glyph| glyph.unwrap_string().chars().next())
+    }
+
+    /// Get the glyphs to draw in place of spaces and tabs, if whitespace is shown
+    fn whitespace_glyphs(&self) -> Option<(char, char)> {
+        let show = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_SHOW_WHITESPACE),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        if !show {
+            return None;
+        }
+        match self.props.get(Attribute::Custom(TEXTAREA_WHITESPACE_CHARS)) {
+            Some(AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(space),
+                PropValue::Str(tab),
+            )))) => Some((
+                space.chars().next().unwrap_or('·'),
+                tab.chars().next().unwrap_or('→'),
+            )),
+            _ => Some(('·', '→')),
+        }
+    }
+
+    /// Truncate `text` to the amount of characters and lines which can still be inserted in the textarea
+    fn fit_limits(&self, text: &str) -> String {
+        let remaining = self
+            .limit(TEXTAREA_MAX_LENGTH)
+            .saturating_sub(self.length());
+        let mut breaks = self
+            .limit(TEXTAREA_MAX_LINES)
+            .saturating_sub(self.widget.lines().len());
+        text.chars()
+            .take(remaining)
+            .take_while(|ch| match ch {
+                '\n' if breaks == 0 => false,
+                '\n' => {
+                    breaks -= 1;
+                    true
                 }
-                CmdResult::None
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Insert `text` at the cursor, splitting it into lines
+    fn insert_text(&mut self, text: &str) {
+        let text = match self.single_line {
+            true => text.split('\n').next().unwrap_or_default(),
+            false => text,
+        };
+        for (i, line) in self.fit_limits(text).split('\n').enumerate() {
+            if i > 0 {
+                self.widget.insert_newline();
             }
-            Cmd::Move(Direction::Left) => {
-                self.widget.move_cursor(CursorMove::Back);
-                CmdResult::None
+            self.widget
+                .insert_str(line.strip_suffix('\r').unwrap_or(line));
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(edit) = self.history.undo() {
+            self.replace_lines(edit.row, edit.after.len(), &edit.before);
+            self.jump(edit.cursor_before);
+            self.notify_change();
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(edit) = self.history.redo() {
+            self.replace_lines(edit.row, edit.before.len(), &edit.after);
+            self.jump(edit.cursor_after);
+            self.notify_change();
+        }
+    }
+
+    /// Call the change callback, if any
+    fn notify_change(&mut self) {
+        // the callback is taken out of the textarea while it borrows it
+        if let Some(mut on_change) = self.on_change.take() {
+            on_change(self);
+            self.on_change = Some(on_change);
+        }
+    }
+
+    /// Get the position at the end of the last line
+    fn end_position(&self) -> (usize, usize) {
+        let lines = self.widget.lines();
+        let row = lines.len() - 1;
+        (row, lines[row].chars().count())
+    }
+
+    /// Move the cursor to `(row, col)`, clamped to the text bounds
+    fn jump(&mut self, position: (usize, usize)) {
+        self.auto_closed.clear();
+        self.widget.cancel_selection();
+        self.move_to(position);
+    }
+
+    /// Move the cursor to `(row, col)`, clamped to the text, keeping the selection if any.
+    /// `CursorMove::Jump` only takes `u16` positions, so the positions beyond are reached by steps
+    fn move_to(&mut self, (row, col): (usize, usize)) {
+        const MAX: usize = u16::MAX as usize;
+        if row <= MAX && col <= MAX {
+            self.widget
+                .move_cursor(CursorMove::Jump(row as u16, col as u16));
+            return;
+        }
+        let last = self.widget.lines().len() - 1;
+        let row = row.min(last);
+        if row <= MAX {
+            self.widget.move_cursor(CursorMove::Jump(row as u16, 0));
+        } else if row - MAX < last - row {
+            self.widget.move_cursor(CursorMove::Jump(u16::MAX, 0));
+            (MAX..row).for_each(|_| self.widget.move_cursor(CursorMove::Down));
+        } else {
+            self.widget.move_cursor(CursorMove::Bottom);
+            (row..last).for_each(|_| self.widget.move_cursor(CursorMove::Up));
+        }
+        // the column is reached backward from the end of the line, since moving forward counts
+        // the chars of the line on each step
+        let len = self.widget.lines()[row].chars().count();
+        self.widget.move_cursor(CursorMove::End);
+        (col.min(len)..len).for_each(|_| self.widget.move_cursor(CursorMove::Back));
+    }
+
+    /// Move the cursor to the text at the terminal position of `TEXTAREA_CLICK_POSITION`, if any
+    fn click(&mut self) {
+        self.drag_anchor = None;
+        if let Some(position) = self.click_position() {
+            self.jump(position);
+        }
+    }
+
+    /// Select the text from the cursor, when the drag starts, to the terminal position of
+    /// `TEXTAREA_CLICK_POSITION`
+    fn drag(&mut self) {
+        if let Some(position) = self.click_position() {
+            let anchor = *self.drag_anchor.get_or_insert(self.widget.cursor());
+            self.select(anchor, position);
+        }
+    }
+
+    /// Get the text position at the terminal position of `TEXTAREA_CLICK_POSITION`, if any
+    fn click_position(&self) -> Option<(usize, usize)> {
+        match self.props.get(Attribute::Custom(TEXTAREA_CLICK_POSITION)) {
+            Some(AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Usize(x),
+                PropValue::Usize(y),
+            )))) => Renderer::new(&self.widget)
+                .line_wrap(self.wraps_lines())
+                .wrap_indent(self.wraps_indent())
+                .line_number_width(self.line_number_columns())
+                .position_at(
+                    self.area,
+                    &self.viewport,
+                    (
+                        x.min(u16::MAX as usize) as u16,
+                        y.min(u16::MAX as usize) as u16,
+                    ),
+                ),
+            _ => None,
+        }
+    }
+
+    /// Scroll the viewport by `TEXTAREA_SCROLL_DELTA` times the scroll step, without moving the cursor.
+    /// The viewport is kept within the text, as it was rendered last
+    fn scroll_mouse(&mut self) {
+        if let Some(AttrValue::Payload(PropPayload::One(PropValue::Isize(delta)))) =
+            self.props.get(Attribute::Custom(TEXTAREA_SCROLL_DELTA))
+        {
+            let step = self
+                .props
+                .get_or(Attribute::ScrollStep, AttrValue::Length(8))
+                .unwrap_length() as isize;
+            let height = Renderer::new(&self.widget).text_area(self.area).height as usize;
+            let max_row = self.widget.lines().len().saturating_sub(height.max(1));
+            let row = (self.viewport.row as isize).saturating_add(delta.saturating_mul(step));
+            self.viewport.set_row((row.max(0) as usize).min(max_row));
+            self.follow_cursor = false;
+        }
+    }
+
+    /// Move the cursor and the viewport by the rendered height minus one row, or by the scroll step
+    /// before the first render
+    fn scroll_page(&mut self, direction: Direction) {
+        let height = Renderer::new(&self.widget).text_area(self.area).height as usize;
+        let page = match height {
+            0 => self
+                .props
+                .get_or(Attribute::ScrollStep, AttrValue::Length(8))
+                .unwrap_length(),
+            height => height.saturating_sub(1).max(1),
+        };
+        let max_row = self.widget.lines().len().saturating_sub(height.max(1));
+        if direction == Direction::Down {
+            (0..page).for_each(|_| self.widget.move_cursor(CursorMove::Down));
+            self.viewport
+                .set_row((self.viewport.row + page).min(max_row));
+        } else {
+            (0..page).for_each(|_| self.widget.move_cursor(CursorMove::Up));
+            self.viewport
+                .set_row(self.viewport.row.saturating_sub(page));
+        }
+    }
+
+    /// Move the cursor as set by `TEXTAREA_FOCUS_CURSOR_BEHAVIOR`, once the textarea has gained focus
+    fn move_cursor_on_focus(&mut self) {
+        let behavior = self
+            .props
+            .get(Attribute::Custom(TEXTAREA_FOCUS_CURSOR_BEHAVIOR))
+            .and_then(|behavior| behavior.unwrap_string().parse().ok())
+            .unwrap_or_default();
+        match behavior {
+            FocusCursorBehavior::Keep => {}
+            FocusCursorBehavior::ToEnd => {
+                let last_row = self.widget.lines().len() - 1;
+                self.jump((last_row, usize::MAX));
             }
-            Cmd::Move(Direction::Right) => {
-                self.widget.move_cursor(CursorMove::Forward);
-                CmdResult::None
+            FocusCursorBehavior::ToStart => self.jump((0, 0)),
+        }
+    }
+
+    /// Get whether the lines longer than the textarea are wrapped
+    fn wraps_lines(&self) -> bool {
+        self.props
+            .get_or(
+                Attribute::Custom(TEXTAREA_LINE_WRAP),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag()
+    }
+
+    /// Whether the rows continuing a wrapped line are indented
+    fn wraps_indent(&self) -> bool {
+        self.props
+            .get_or(
+                Attribute::Custom(TEXTAREA_WRAP_INDENT),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag()
+    }
+
+    /// Select the text between `from` and `to`; the cursor is left at `to`
+    fn select(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.jump(from);
+        self.widget.start_selection();
+        self.move_to(to);
+    }
+
+    /// Replace `count` lines starting from `row` with `lines`
+    fn replace_lines(&mut self, row: usize, count: usize, lines: &[String]) {
+        let len = self.widget.lines().len();
+        if row + count < len {
+            // replace lines including their line breaks
+            self.select((row, 0), (row + count, 0));
+            let text = lines.iter().map(|x| format!("{x}\n")).collect::<String>();
+            self.widget.insert_str(text);
+        } else if row > 0 {
+            // replace lines up to the end, along with the line break before them
+            self.select((row - 1, usize::MAX), (len - 1, usize::MAX));
+            let text = lines.iter().map(|x| format!("\n{x}")).collect::<String>();
+            self.widget.insert_str(text);
+        } else {
+            self.select((0, 0), (len - 1, usize::MAX));
+            self.widget.insert_str(lines.join("\n"));
+        }
+        self.widget.cancel_selection();
+    }
+
+    /// Get the first and the last row affected by line-wise commands: the rows of the selection
+    /// or the current row.
+    /// A selection ending at the head of a line doesn't include that line
+    fn selected_rows(&self) -> (usize, usize) {
+        match self.widget.selection_range() {
+            Some(((start, _), (end, 0))) if end > start => (start, end - 1),
+            Some(((start, _), (end, _))) => (start, end),
+            None => (self.widget.cursor().0, self.widget.cursor().0),
+        }
+    }
+
+    /// Rewrite the lines affected by line-wise commands with `f`, as a single history step.
+    /// The cursor and the selection are moved along with the text
+    fn map_selected_lines<F>(&mut self, f: F)
+    where
+        F: Fn(&str) -> String,
+    {
+        let (first, last) = self.selected_rows();
+        let selection = self.widget.selection_range().map(|(start, end)| {
+            if start == self.widget.cursor() {
+                (end, start)
+            } else {
+                (start, end)
             }
-            Cmd::Move(Direction::Up) => {
-                if !self.single_line {
-                    self.widget.move_cursor(CursorMove::Up);
-                }
-                CmdResult::None
+        });
+        let before = self.widget.lines()[first..=last].to_vec();
+        let after: Vec<String> = before.iter().map(|x| f(x)).collect();
+        let remap = |(row, col): (usize, usize)| match row.checked_sub(first) {
+            Some(i) if row <= last => (row, indent::reindent_col(&before[i], &after[i], col)),
+            _ => (row, col),
+        };
+        let cursor = remap(self.widget.cursor());
+        self.record(first..last + 1, false, |this| {
+            this.replace_lines(first, before.len(), &after);
+            match selection {
+                Some((anchor, _)) => this.select(remap(anchor), cursor),
+                None => this.jump(cursor),
             }
-            Cmd::Scroll(Direction::Down) => {
-                if !self.single_line {
-                    let step = self
-                        .props
-                        .get_or(Attribute::ScrollStep, AttrValue::Length(8))
-                        .unwrap_length();
-                    (0..step).for_each(|_| self.widget.move_cursor(CursorMove::Down));
+        });
+    }
+
+    /// Map the chars of the selected text, or of the word at the cursor, with `f`, as a single
+    /// history step. The cursor and the selection are moved along with the text, whose length may change
+    fn change_case<F>(&mut self, f: F)
+    where
+        F: Fn(char) -> String,
+    {
+        let cursor = self.widget.cursor();
+        let selection = self.widget.selection_range();
+        let (start, end) = match selection {
+            Some(range) => range,
+            None => match word::word_at(&self.widget.lines()[cursor.0], cursor.1) {
+                Some((start, end)) => ((cursor.0, start), (cursor.0, end)),
+                None => return,
+            },
+        };
+        let mut after = Vec::with_capacity(end.0 - start.0 + 1);
+        // the new column of each char, and of the end of each line
+        let mut cols = Vec::with_capacity(end.0 - start.0 + 1);
+        for (row, line) in (start.0..=end.0).zip(&self.widget.lines()[start.0..=end.0]) {
+            let mut text = String::with_capacity(line.len());
+            let mut line_cols = vec![0];
+            for (col, ch) in line.chars().enumerate() {
+                match (start..end).contains(&(row, col)) {
+                    true => text.push_str(&f(ch)),
+                    false => text.push(ch),
                 }
-                CmdResult::None
+                line_cols.push(text.chars().count());
             }
-            Cmd::Scroll(Direction::Up) => {
-                if !self.single_line {
-                    let step = self
-                        .props
-                        .get_or(Attribute::ScrollStep, AttrValue::Length(8))
-                        .unwrap_length();
-                    (0..step).for_each(|_| self.widget.move_cursor(CursorMove::Up));
+            after.push(text);
+            cols.push(line_cols);
+        }
+        let remap = |(row, col): (usize, usize)| match row.checked_sub(start.0) {
+            Some(i) if row <= end.0 => (row, cols[i][col.min(cols[i].len() - 1)]),
+            _ => (row, col),
+        };
+        self.record(start.0..end.0 + 1, false, |this| {
+            this.replace_lines(start.0, after.len(), &after);
+            match selection {
+                Some((start, end)) if start == cursor => this.select(remap(end), remap(cursor)),
+                Some((start, _)) => this.select(remap(start), remap(cursor)),
+                None => this.jump(remap(cursor)),
+            }
+        });
+    }
+
+    /// Rewrite the indentation of the selected lines with tabs or spaces
+    fn retab_selection(&mut self) {
+        let tab_len = self.widget.tab_length();
+        let hard_tab = self.widget.hard_tab_indent();
+        if tab_len > 0 {
+            self.map_selected_lines(|line| indent::retab(line, tab_len, hard_tab));
+        }
+    }
+
+    /// Indent the selected lines by one tab stop, with a tab or spaces
+    fn indent_selection(&mut self) {
+        let indent = match self.widget.hard_tab_indent() {
+            true => String::from("\t"),
+            false => " ".repeat(self.widget.tab_length() as usize),
+        };
+        if !indent.is_empty() {
+            self.map_selected_lines(|line| format!("{indent}{line}"));
+        }
+    }
+
+    /// Remove up to one tab stop of indentation from the selected lines
+    fn dedent_selection(&mut self) {
+        let tab_len = self.widget.tab_length();
+        self.map_selected_lines(|line| indent::dedent(line, tab_len).to_string());
+    }
+
+    /// Comment or uncomment each of the selected lines with the comment prefix, if set
+    fn toggle_comment(&mut self) {
+        if let Some(AttrValue::String(prefix)) =
+            self.props.get(Attribute::Custom(TEXTAREA_COMMENT_PREFIX))
+        {
+            if !prefix.is_empty() {
+                self.map_selected_lines(|line| comment::toggle_comment(line, &prefix));
+            }
+        }
+    }
+
+    /// Wrap the selected text in the block comment pair, or unwrap it if it's already wrapped.
+    /// Without a selection, the text of the cursor line after its indentation is toggled
+    fn toggle_block_comment(&mut self) {
+        let Some(AttrValue::Payload(PropPayload::Tup2((
+            PropValue::Str(open),
+            PropValue::Str(close),
+        )))) = self.props.get(Attribute::Custom(TEXTAREA_BLOCK_COMMENT))
+        else {
+            return;
+        };
+        let (Some((start, end)), Some(text)) =
+            (self.widget.selection_range(), self.selected_text())
+        else {
+            self.map_selected_lines(|line| comment::toggle_block_comment_line(line, &open, &close));
+            return;
+        };
+        let lines = self.widget.lines();
+        let before: String = lines[start.0].chars().take(start.1).collect();
+        let after: String = lines[end.0].chars().skip(end.1).collect();
+        let text = comment::toggle_block_comment(&text, &open, &close);
+        let toggled: Vec<String> = format!("{before}{text}{after}")
+            .split('\n')
+            .map(String::from)
+            .collect();
+        // the end of the toggled text, which stays selected
+        let last = toggled.len() - 1;
+        let end_col = toggled[last].chars().count() - after.chars().count();
+        let new_end = (start.0 + last, end_col);
+        let cursor_at_start = self.widget.cursor() == start;
+        self.record(start.0..end.0 + 1, false, |this| {
+            this.replace_lines(start.0, end.0 - start.0 + 1, &toggled);
+            match cursor_at_start {
+                true => this.select(new_end, start),
+                false => this.select(start, new_end),
+            }
+        });
+    }
+
+    /// Remove the trailing spaces and tabs from all the lines.
+    /// The cursor is clamped to the end of its line, if it was in the removed whitespace
+    fn trim_trailing_whitespace(&mut self) {
+        let lines: Vec<String> = self
+            .widget
+            .lines()
+            .iter()
+            .map(|x| x.trim_end_matches([' ', '\t']).to_string())
+            .collect();
+        if lines != self.widget.lines() {
+            let cursor = self.widget.cursor();
+            self.replace_lines(0, lines.len(), &lines);
+            self.jump(cursor);
+        }
+    }
+
+    /// Replace the tabs in all the lines with the spaces up to the next tab stop
+    fn expand_tabs(&mut self) {
+        let tab_len = self.widget.tab_length();
+        let (row, col) = self.widget.cursor();
+        let lines: Vec<String> = self
+            .widget
+            .lines()
+            .iter()
+            .map(|x| indent::expand_tabs(x, tab_len))
+            .collect();
+        if tab_len > 0 && lines != self.widget.lines() {
+            // the cursor keeps its display column
+            let head: String = self.widget.lines()[row].chars().take(col).collect();
+            let col = indent::expand_tabs(&head, tab_len).chars().count();
+            self.replace_lines(0, lines.len(), &lines);
+            self.jump((row, col));
+        }
+    }
+
+    /// Rewrite the indentation of all the lines with tabs, padded with spaces
+    fn tabify(&mut self) {
+        let tab_len = self.widget.tab_length();
+        let (row, col) = self.widget.cursor();
+        let lines: Vec<String> = self
+            .widget
+            .lines()
+            .iter()
+            .map(|x| indent::retab(x, tab_len, true))
+            .collect();
+        if tab_len > 0 && lines != self.widget.lines() {
+            let col = indent::reindent_col(&self.widget.lines()[row], &lines[row], col);
+            self.replace_lines(0, lines.len(), &lines);
+            self.jump((row, col));
+        }
+    }
+
+    /// Get the pairs of opener and closer which are closed automatically, if enabled
+    fn closing_pairs(&self) -> Vec<(char, char)> {
+        let enabled = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_AUTO_CLOSE),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        if !enabled {
+            return Vec::new();
+        }
+        let pairs: Vec<char> = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_AUTO_CLOSE_PAIRS),
+                AttrValue::String(String::from("()[]{}\"\"''")),
+            )
+            .unwrap_string()
+            .chars()
+            .collect();
+        pairs.chunks_exact(2).map(|x| (x[0], x[1])).collect()
+    }
+
+    /// Get the chars before and after the cursor
+    fn chars_around_cursor(&self) -> (Option<char>, Option<char>) {
+        let (row, col) = self.widget.cursor();
+        let mut chars = self.widget.lines()[row].chars().skip(col.saturating_sub(1));
+        match col {
+            0 => (None, chars.next()),
+            _ => (chars.next(), chars.next()),
+        }
+    }
+
+    /// Type `ch`, inserting its closer after the cursor if it opens a pair, or typing over the
+    /// next char if it's the same closer and it was inserted along with its opener.
+    /// Quotes are not closed right after a word char, such as in `don't`
+    fn type_char(&mut self, ch: char) {
+        let pairs = self.closing_pairs();
+        let cursor = self.widget.cursor();
+        if self.widget.selection_range().is_some() {
+            self.auto_closed.clear();
+        }
+        let (prev, next) = self.chars_around_cursor();
+        if next == Some(ch) && self.auto_closed.last() == Some(&cursor) {
+            self.auto_closed.pop();
+            self.widget.move_cursor(CursorMove::Forward);
+            return;
+        }
+        let after_word = prev.is_some_and(word::is_word_char);
+        let close = pairs
+            .iter()
+            .find(|(open, close)| *open == ch && !(open == close && after_word))
+            .map(|(_, close)| *close);
+        // the closers after the cursor are moved by the inserted chars
+        let (row, col) = cursor;
+        let shift = 1 + close.map_or(0, |_| 1);
+        self.auto_closed
+            .iter_mut()
+            .filter(|(r, c)| *r == row && *c >= col)
+            .for_each(|(_, c)| *c += shift);
+        self.widget.insert_char(ch);
+        if let Some(close) = close {
+            self.widget.insert_char(close);
+            self.widget.move_cursor(CursorMove::Back);
+            self.auto_closed.push(self.widget.cursor());
+        }
+    }
+
+    /// Delete the char before the cursor, along with the next one if it's the closer inserted along
+    /// with it
+    fn delete_char(&mut self) {
+        let (row, col) = self.widget.cursor();
+        if self.widget.selection_range().is_some() || col == 0 {
+            self.auto_closed.clear();
+        }
+        let mut deleted = 1;
+        if self.auto_closed.last() == Some(&(row, col)) {
+            if let (Some(prev), Some(next)) = self.chars_around_cursor() {
+                if self.closing_pairs().contains(&(prev, next)) {
+                    self.auto_closed.pop();
+                    self.widget.delete_next_char();
+                    deleted = 2;
                 }
-                CmdResult::None
             }
-            Cmd::Type('\t') => {
-                self.widget.insert_tab();
-                CmdResult::None
+        }
+        // the closers after the cursor are moved back by the deleted chars
+        self.auto_closed
+            .iter_mut()
+            .filter(|(r, c)| *r == row && *c >= col)
+            .for_each(|(_, c)| *c -= deleted);
+        self.widget.delete_char();
+    }
+
+    /// Insert a copy of the selected text after the selection, selecting the copy.
+    /// Without a selection, the current line is duplicated, unless in single line mode
+    fn duplicate_selection(&mut self) {
+        let (Some((_, end)), Some(text)) = (self.widget.selection_range(), self.selected_text())
+        else {
+            if !self.single_line {
+                self.duplicate_line();
             }
-            Cmd::Type('\n') | Cmd::Custom(TEXTAREA_CMD_NEWLINE) => {
-                if !self.single_line {
-                    self.widget.insert_newline();
+            return;
+        };
+        self.jump(end);
+        self.insert_text(&text);
+        let cursor = self.widget.cursor();
+        self.select(end, cursor);
+    }
+
+    /// Insert a copy of the current line below it, moving the cursor to the copy
+    fn duplicate_line(&mut self) {
+        let (row, col) = self.widget.cursor();
+        let line = self.widget.lines()[row].clone();
+        self.widget.cancel_selection();
+        self.widget.move_cursor(CursorMove::End);
+        self.widget.insert_newline();
+        self.widget.insert_str(line);
+        self.jump((row + 1, col));
+    }
+
+    /// Swap the current line with the one above if `up`, otherwise with the one below.
+    /// The cursor is moved along with the line
+    fn move_line(&mut self, up: bool) {
+        let (row, col) = self.widget.cursor();
+        let other = match up {
+            true => row.checked_sub(1),
+            false => Some(row + 1).filter(|x| *x < self.widget.lines().len()),
+        };
+        if let Some(other) = other {
+            let first = row.min(other);
+            let lines = self.widget.lines();
+            let swapped = [lines[first + 1].clone(), lines[first].clone()];
+            self.replace_lines(first, 2, &swapped);
+            self.jump((other, col));
+        }
+    }
+
+    /// Append the next line to the current one, without its indentation, putting the join separator
+    /// between them unless the next line is blank. The cursor is moved to the join point
+    fn join_lines(&mut self) {
+        let (row, _) = self.widget.cursor();
+        let lines = self.widget.lines();
+        if row + 1 < lines.len() {
+            let next = lines[row + 1].trim_start_matches([' ', '\t']);
+            let separator = match next.is_empty() {
+                true => String::new(),
+                false => self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_JOIN_SEPARATOR),
+                        AttrValue::String(String::from(" ")),
+                    )
+                    .unwrap_string(),
+            };
+            let col = lines[row].chars().count();
+            let joined = format!("{}{separator}{next}", lines[row]);
+            self.replace_lines(row, 2, &[joined]);
+            self.jump((row, col));
+        }
+    }
+
+    /// Delete the text between the cursor and `to`, leaving the cursor at the cut point
+    fn delete_to(&mut self, to: (usize, usize)) {
+        let cursor = self.widget.cursor();
+        self.select(cursor, to);
+        // an empty selection would delete the char before the cursor
+        if self
+            .widget
+            .selection_range()
+            .is_some_and(|(start, end)| start != end)
+        {
+            self.widget.delete_char();
+        } else {
+            self.jump(cursor);
+        }
+    }
+
+    /// Swap the char before the cursor with the one at the cursor, moving the cursor after both.
+    /// At the end of the line the two chars before the cursor are swapped, as in Emacs, while at the
+    /// head of the line nothing is done
+    fn transpose_chars(&mut self) {
+        let (row, col) = self.widget.cursor();
+        let mut chars: Vec<char> = self.widget.lines()[row].chars().collect();
+        let col = match col {
+            0 => return,
+            col if col == chars.len() => col - 1,
+            col => col,
+        };
+        if col > 0 {
+            chars.swap(col - 1, col);
+            self.replace_lines(row, 1, &[chars.into_iter().collect()]);
+            self.jump((row, col + 1));
+        }
+    }
+
+    /// Whether `cmd` is one of `TEXTAREA_DISABLED_CMDS`
+    fn is_disabled_cmd(&self, cmd: &Cmd) -> bool {
+        let Cmd::Custom(cmd) = cmd else {
+            return false;
+        };
+        match self.props.get(Attribute::Custom(TEXTAREA_DISABLED_CMDS)) {
+            Some(AttrValue::Payload(PropPayload::Vec(cmds))) => cmds
+                .iter()
+                .any(|disabled| matches!(disabled, PropValue::Str(disabled) if disabled == cmd)),
+            _ => false,
+        }
+    }
+
+    /// Whether `cmd` changes the text
+    fn is_edit_cmd(cmd: &Cmd) -> bool {
+        match cmd {
+            Cmd::Cancel | Cmd::Delete | Cmd::Type(_) => true,
+            Cmd::Custom(cmd) => [
+                TEXTAREA_CMD_NEWLINE,
+                TEXTAREA_CMD_DEL_LINE_BY_END,
+                TEXTAREA_CMD_DEL_LINE_BY_HEAD,
+                TEXTAREA_CMD_DEL_WORD,
+                TEXTAREA_CMD_DEL_NEXT_WORD,
+                TEXTAREA_CMD_DEL_TO_BOTTOM,
+                TEXTAREA_CMD_DEL_TO_TOP,
+                TEXTAREA_CMD_PASTE_FROM_REGISTER,
+                TEXTAREA_CMD_UNDO,
+                TEXTAREA_CMD_REDO,
+                #[cfg(feature = "clipboard")]
+                TEXTAREA_CMD_CUT,
+                #[cfg(feature = "clipboard")]
+                TEXTAREA_CMD_PASTE,
+                TEXTAREA_CMD_RETAB_SELECTION,
+                TEXTAREA_CMD_INDENT,
+                TEXTAREA_CMD_DEDENT,
+                TEXTAREA_CMD_TOGGLE_COMMENT,
+                TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT,
+                TEXTAREA_CMD_INSERT_HARD_TAB,
+                TEXTAREA_CMD_TRIM_TRAILING_WHITESPACE,
+                TEXTAREA_CMD_INSERT_STR,
+                TEXTAREA_CMD_JOIN_LINES,
+                TEXTAREA_CMD_EXPAND_TABS,
+                TEXTAREA_CMD_TABIFY,
+                TEXTAREA_CMD_DELETE_SELECTION,
+                TEXTAREA_CMD_CLEAR,
+                TEXTAREA_CMD_TRANSPOSE_CHARS,
+                TEXTAREA_CMD_UPPERCASE,
+                TEXTAREA_CMD_LOWERCASE,
+                TEXTAREA_CMD_TOGGLE_CASE,
+                TEXTAREA_CMD_DUPLICATE_LINE,
+                TEXTAREA_CMD_DUPLICATE_SELECTION,
+                TEXTAREA_CMD_MOVE_LINE_UP,
+                TEXTAREA_CMD_MOVE_LINE_DOWN,
+                #[cfg(feature = "search")]
+                TEXTAREA_CMD_REPLACE_NEXT,
+                #[cfg(feature = "search")]
+                TEXTAREA_CMD_REPLACE_ALL,
+            ]
+            .contains(cmd),
+            _ => false,
+        }
+    }
+
+    /// Move the cursor to the first non-blank char of `row`, if any
+    fn jump_to_indent(&mut self, row: Option<usize>) {
+        if let Some(row) = row {
+            let col = indent::leading_whitespace(&self.widget.lines()[row])
+                .chars()
+                .count();
+            self.jump((row, col));
+        }
+    }
+
+    #[cfg(feature = "search")]
+    /// Make the value of the search options property
+    fn search_options_value(options: SearchOptions) -> AttrValue {
+        AttrValue::Payload(PropPayload::Tup4((
+            PropValue::Bool(options.regex),
+            PropValue::Bool(options.case_insensitive),
+            PropValue::Bool(options.whole_word),
+            PropValue::Bool(options.wrap),
+        )))
+    }
+
+    #[cfg(feature = "search")]
+    /// Set the search options, keeping the search options property in sync, and update the search pattern
+    fn set_search_options(&mut self, options: SearchOptions) {
+        self.search_options = options;
+        self.props.set(
+            Attribute::Custom(TEXTAREA_SEARCH_OPTIONS),
+            Self::search_options_value(options),
+        );
+        self.update_search_pattern();
+    }
+
+    #[cfg(feature = "search")]
+    /// Set the search pattern on the widget, built from the pattern property and the search options.
+    /// An invalid pattern leaves the previous one active
+    fn update_search_pattern(&mut self) {
+        if let Some(AttrValue::String(pattern)) =
+            self.query(Attribute::Custom(TEXTAREA_SEARCH_PATTERN))
+        {
+            let _ = self
+                .widget
+                .set_search_pattern(self.search_options.pattern(&pattern));
+        }
+    }
+
+    #[cfg(feature = "search")]
+    /// Move to the next or the previous search match, going back to the previous position if
+    /// the search wrapped around but wrapping is disabled
+    fn search(&mut self, forward: bool) {
+        let cursor = self.widget.cursor();
+        let found = match forward {
+            true => self.widget.search_forward(true),
+            false => self.widget.search_back(true),
+        };
+        let wrapped = match forward {
+            true => self.widget.cursor() < cursor,
+            false => self.widget.cursor() > cursor,
+        };
+        if found && wrapped && !self.search_options.wrap {
+            self.jump(cursor);
+        }
+        let center = self
+            .props
+            .get_or(
+                Attribute::Custom(TEXTAREA_SEARCH_CENTER),
+                AttrValue::Flag(false),
+            )
+            .unwrap_flag();
+        if center && self.widget.cursor() != cursor {
+            self.center_viewport();
+        }
+    }
+
+    #[cfg(feature = "search")]
+    /// Scroll the viewport to put the cursor row in the middle of the area rendered last,
+    /// without scrolling past the end of the text
+    fn center_viewport(&mut self) {
+        let height = Renderer::new(&self.widget).text_area(self.area).height as usize;
+        let max_row = self.widget.lines().len().saturating_sub(height.max(1));
+        self.viewport.set_row(
+            self.widget
+                .cursor()
+                .0
+                .saturating_sub(height / 2)
+                .min(max_row),
+        );
+        self.follow_cursor = true;
+    }
+
+    #[cfg(feature = "search")]
+    /// Get the text replacing the search matches
+    fn replacement(&self) -> String {
+        match self.query(Attribute::Custom(TEXTAREA_REPLACE_WITH)) {
+            Some(AttrValue::String(replacement)) => replacement,
+            _ => String::new(),
+        }
+    }
+
+    #[cfg(feature = "search")]
+    /// Replace the search match at the cursor, or the next one, then move to the following match.
+    /// When the pattern is a regex, the replacement can refer to capture groups, such as `$1`
+    fn replace_next(&mut self) {
+        self.search(true);
+        let (row, col) = self.widget.cursor();
+        let Some((end, text)) = self.widget.search_pattern().and_then(|pattern| {
+            let line = &self.widget.lines()[row];
+            let start = line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            let captures = pattern.captures_at(line, start)?;
+            let matched = captures
+                .get(0)
+                .filter(|m| m.start() == start && !m.is_empty())?;
+            let mut text = String::new();
+            match self.search_options.regex {
+                true => captures.expand(&self.replacement(), &mut text),
+                false => text = self.replacement(),
+            }
+            Some((col + matched.as_str().chars().count(), text))
+        }) else {
+            return;
+        };
+        self.select((row, col), (row, end));
+        self.widget.insert_str(text);
+        self.search(true);
+    }
+
+    #[cfg(feature = "search")]
+    /// Replace all the search matches
+    fn replace_all(&mut self) {
+        let Some(pattern) = self.widget.search_pattern() else {
+            return;
+        };
+        let replacement = self.replacement();
+        let lines: Vec<String> = self
+            .widget
+            .lines()
+            .iter()
+            .map(|line| match self.search_options.regex {
+                true => pattern.replace_all(line, replacement.as_str()).into_owned(),
+                false => pattern
+                    .replace_all(line, lazy_regex::regex::NoExpand(&replacement))
+                    .into_owned(),
+            })
+            .collect();
+        if lines != self.widget.lines() {
+            let cursor = self.widget.cursor();
+            self.replace_lines(0, self.widget.lines().len(), &lines);
+            self.jump(cursor);
+        }
+    }
+
+    /// Get the register set with `TEXTAREA_REGISTER`, or the unnamed register if unset
+    fn register_name(&self) -> char {
+        self.props
+            .get(Attribute::Custom(TEXTAREA_REGISTER))
+            .and_then(|name| name.unwrap_string().chars().next())
+            .unwrap_or(UNNAMED_REGISTER)
+    }
+
+    /// Copy the selected text, or the current line along with its line break if there's no
+    /// selection, to the register set with `TEXTAREA_REGISTER` and to the unnamed register
+    fn yank_to_register(&mut self) {
+        let text = self.selected_text().unwrap_or_else(|| {
+            let line = &self.widget.lines()[self.widget.cursor().0];
+            format!("{line}\n")
+        });
+        self.registers.insert(self.register_name(), text.clone());
+        self.registers.insert(UNNAMED_REGISTER, text);
+    }
+
+    #[cfg(feature = "clipboard")]
+    /// Copy the selected text, or the current line along with its line break if there's no
+    /// selection, to the yank buffer and to the system clipboard
+    fn copy(&mut self) {
+        if self.widget.selection_range().is_some() {
+            self.widget.copy();
+        } else {
+            let line = &self.widget.lines()[self.widget.cursor().0];
+            self.widget.set_yank_text(format!("{line}\n"));
+        }
+        self.set_clipboard(self.widget.yank_text());
+    }
+
+    #[cfg(feature = "clipboard")]
+    /// Cut the selected text, or the current line along with its line break if there's no
+    /// selection, to the yank buffer and to the system clipboard
+    fn cut(&mut self) {
+        if self.widget.selection_range().is_some() {
+            self.widget.cut();
+        } else {
+            let (row, _) = self.widget.cursor();
+            let len = self.widget.lines().len();
+            let line = format!("{}\n", self.widget.lines()[row]);
+            if row + 1 < len {
+                self.select((row, 0), (row + 1, 0));
+            } else if row > 0 {
+                // the last line has no line break, so the one before it is removed
+                self.select((row - 1, usize::MAX), (row, usize::MAX));
+            } else {
+                self.select((row, 0), (row, usize::MAX));
+            }
+            self.widget.cut();
+            self.widget.set_yank_text(line);
+        }
+        self.set_clipboard(self.widget.yank_text());
+    }
+
+    #[cfg(feature = "clipboard")]
+    /// Get the system clipboard, connecting to it on first use.
+    /// If the connection fails, it is attempted again the next time
+    fn clipboard(&mut self) -> Option<&mut ClipboardContext> {
+        if self.clipboard.is_none() {
+            self.clipboard = ClipboardContext::new().ok();
+        }
+        self.clipboard.as_mut()
+    }
+
+    #[cfg(feature = "clipboard")]
+    /// Write `text` to the system clipboard, if available
+    fn set_clipboard(&mut self, text: String) {
+        if let Some(ctx) = self.clipboard() {
+            let _ = ctx.set_contents(text);
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    /// Paste the content of the system clipboard, or of the yank buffer if the clipboard is not available
+    fn paste(&mut self) {
+        let text = self
+            .clipboard()
+            .and_then(|ctx| ctx.get_contents().ok())
+            .unwrap_or_else(|| self.widget.yank_text());
+        self.insert_text(&text);
+    }
+}
+
+impl<'a> MockComponent for TextArea<'a> {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            // set block
+            if let Some(block) = self.get_block() {
+                self.widget.set_block(block);
+            }
+            let (horizontal_margin, vertical_margin) = self.layout_margins_size();
+            // make chunks
+            let chunks = Layout::default()
+                .direction(LayoutDirection::Vertical)
+                .horizontal_margin(horizontal_margin)
+                .vertical_margin(vertical_margin)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(
+                            self.status_fmt
+                                .as_ref()
+                                .map_or(0, |_| self.bar_height(TEXTAREA_STATUS_HEIGHT)),
+                        ),
+                        Constraint::Length(
+                            self.footer_fmt
+                                .as_ref()
+                                .map_or(0, |_| self.bar_height(TEXTAREA_FOOTER_HEIGHT)),
+                        ),
+                    ]
+                    .as_ref(),
+                )
+                .split(area)
+                .iter()
+                .map(|chunk| self.center_max_width(*chunk))
+                .collect::<Vec<Rect>>();
+
+            // Remove cursor if not in focus
+            let focus = self
+                .props
+                .get_or(Attribute::Focus, AttrValue::Flag(false))
+                .unwrap_flag();
+            if !focus {
+                let style = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_UNFOCUSED_CURSOR_STYLE),
+                        AttrValue::Style(Style::reset()),
+                    )
+                    .unwrap_style();
+                self.widget.set_cursor_style(style);
+            } else {
+                let style = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_CURSOR_STYLE),
+                        AttrValue::Style(Style::default().add_modifier(TextModifiers::REVERSED)),
+                    )
+                    .unwrap_style();
+                self.widget.set_cursor_style(style);
+            }
+
+            // render widget
+            let (text_chunk, scrollbar) = self.split_scrollbar(chunks[0]);
+            let cursor_gutter_style = self
+                .props
+                .get_or(
+                    Attribute::Custom(TEXTAREA_CURSOR_GUTTER_STYLE),
+                    AttrValue::Style(Style::default()),
+                )
+                .unwrap_style();
+            let renderer = Renderer::new(&self.widget)
+                .cursor_gutter_style(cursor_gutter_style)
+                .line_wrap(self.wraps_lines())
+                .wrap_indent(self.wraps_indent())
+                .follow_cursor(self.follow_cursor)
+                .relative_line_numbers(
+                    self.props
+                        .get_or(
+                            Attribute::Custom(TEXTAREA_RELATIVE_LINE_NUMBERS),
+                            AttrValue::Flag(false),
+                        )
+                        .unwrap_flag(),
+                )
+                .line_number_width(self.line_number_columns())
+                .highlights(
+                    // brackets are patched over the words
+                    [
+                        self.word_highlights(text_chunk.height as usize),
+                        self.bracket_highlights(),
+                    ]
+                    .concat(),
+                )
+                .scroll_off(
+                    self.props
+                        .get_or(Attribute::Custom(TEXTAREA_SCROLL_OFF), AttrValue::Length(0))
+                        .unwrap_length(),
+                )
+                .center_cursor(
+                    self.props
+                        .get_or(
+                            Attribute::Custom(TEXTAREA_CENTER_CURSOR),
+                            AttrValue::Flag(false),
+                        )
+                        .unwrap_flag(),
+                )
+                .line_styler(self.line_styler.as_ref())
+                .line_highlights(Some(&self.line_highlights))
+                .whitespace(self.whitespace_glyphs())
+                .tab_char(self.tab_glyph())
+                .rulers(
+                    self.ruler_columns(),
+                    self.props
+                        .get_or(
+                            Attribute::Custom(TEXTAREA_RULER_STYLE),
+                            AttrValue::Style(Style::default().add_modifier(TextModifiers::DIM)),
+                        )
+                        .unwrap_style(),
+                )
+                .indent_guides(self.indent_guides_style());
+            frame.render_stateful_widget(renderer, text_chunk, &mut self.viewport);
+            self.area = text_chunk;
+            if let Some(area) = scrollbar {
+                self.render_scrollbar(frame, area);
+            }
+            let modified = self.is_modified().then(|| {
+                self.props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_MODIFIED_MARKER),
+                        AttrValue::String(String::from("[+]")),
+                    )
+                    .unwrap_string()
+            });
+            let filename = self
+                .props
+                .get(Attribute::Custom(TEXTAREA_FILENAME))
+                .map(|x| x.unwrap_string());
+            for fmt in [self.status_fmt.as_mut(), self.footer_fmt.as_mut()]
+                .into_iter()
+                .flatten()
+            {
+                fmt.set_filename(filename.clone());
+                fmt.set_modified(modified.clone());
+                fmt.set_time_fmt(self.time_fmt.clone());
+            }
+            for (fmt, area) in [
+                (self.status_fmt.as_ref(), chunks[1]),
+                (self.footer_fmt.as_ref(), chunks[2]),
+            ] {
+                if let Some(fmt) = fmt {
+                    let paragraph = Paragraph::new(fmt.fmt(&self.widget)).style(fmt.style());
+                    // bars taller than a row wrap their text instead of truncating it
+                    let paragraph = match area.height > 1 {
+                        true => paragraph.wrap(Wrap { trim: false }),
+                        false => paragraph,
+                    };
+                    frame.render_widget(paragraph, area);
                 }
-                CmdResult::None
             }
-            Cmd::Type(ch) => {
-                self.widget.insert_char(ch);
-                CmdResult::None
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        let focused = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        self.props.set(attr, value.clone());
+        match (attr, value) {
+            (Attribute::Focus, AttrValue::Flag(true)) if !focused => {
+                self.move_cursor_on_focus();
             }
-            Cmd::Submit => CmdResult::Submit(self.state()),
-            _ => CmdResult::None,
+            (
+                Attribute::Custom(TEXTAREA_FOOTER_FMT),
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Str(fmt),
+                    PropValue::Style(style),
+                ))),
+            ) => {
+                self.footer_fmt = Some(LineFmt::new(&fmt, style, &self.fmt_keys));
+            }
+            (
+                Attribute::Custom(TEXTAREA_MAX_HISTORY),
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(max))),
+            ) => {
+                self.history.set_max_items(max);
+            }
+            (
+                Attribute::Custom(TEXTAREA_STATUS_FMT),
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Str(fmt),
+                    PropValue::Style(style),
+                ))),
+            ) => {
+                self.status_fmt = Some(LineFmt::new(&fmt, style, &self.fmt_keys));
+            }
+            (Attribute::Custom(TEXTAREA_SINGLE_LINE), AttrValue::Flag(single_line)) => {
+                self.single_line = single_line;
+            }
+            (Attribute::Custom(TEXTAREA_READONLY), AttrValue::Flag(read_only)) => {
+                self.read_only = read_only;
+            }
+            #[cfg(feature = "search")]
+            (
+                Attribute::Custom(TEXTAREA_SEARCH_OPTIONS),
+                AttrValue::Payload(PropPayload::Tup4((
+                    PropValue::Bool(regex),
+                    PropValue::Bool(case_insensitive),
+                    PropValue::Bool(whole_word),
+                    PropValue::Bool(wrap),
+                ))),
+            ) => {
+                self.set_search_options(SearchOptions {
+                    regex,
+                    case_insensitive,
+                    whole_word,
+                    wrap,
+                });
+            }
+            #[cfg(feature = "search")]
+            (Attribute::Custom(TEXTAREA_SEARCH_CASE_INSENSITIVE), AttrValue::Flag(enabled)) => {
+                self.set_search_options(SearchOptions {
+                    case_insensitive: enabled,
+                    ..self.search_options
+                });
+            }
+            #[cfg(feature = "search")]
+            (Attribute::Custom(TEXTAREA_SEARCH_REGEX), AttrValue::Flag(enabled)) => {
+                self.set_search_options(SearchOptions {
+                    regex: enabled,
+                    ..self.search_options
+                });
+            }
+            // the other properties may be held by the widget
+            (_, _) => self.update_widget(),
         }
     }
+
+    fn state(&self) -> State {
+        State::Vec(
+            self.widget
+                .lines()
+                .iter()
+                .map(|x| StateValue::String(x.to_string()))
+                .collect(),
+        )
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        if (self.read_only && Self::is_edit_cmd(&cmd)) || self.is_disabled_cmd(&cmd) {
+            return CmdResult::None;
+        }
+        // any command but scrolling the viewport brings the cursor back into view
+        self.follow_cursor = true;
+        // only the chars typed or deleted in a row keep track of the closers which can be typed over
+        if !matches!(cmd, Cmd::Type(ch) if ch != '\t' && ch != '\n') && cmd != Cmd::Delete {
+            self.auto_closed.clear();
+        }
+        match cmd {
+            Cmd::Cancel => {
+                self.edit_near_cursor(|this| {
+                    this.widget.delete_next_char();
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_LINE_BY_END) => {
+                self.edit_near_cursor(|this| {
+                    this.widget.delete_line_by_end();
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_LINE_BY_HEAD) => {
+                self.edit_near_cursor(|this| {
+                    this.widget.delete_line_by_head();
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_NEXT_WORD) => {
+                self.edit_near_cursor(|this| {
+                    this.widget.delete_next_word();
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_WORD) => {
+                self.edit_near_cursor(|this| {
+                    this.widget.delete_word();
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_PARAGRAPH_BACK) => {
+                self.widget.move_cursor(CursorMove::ParagraphBack);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_PARAGRAPH_FORWARD) => {
+                self.widget.move_cursor(CursorMove::ParagraphForward);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_WORD_BACK) => {
+                self.widget.move_cursor(CursorMove::WordBack);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_WORD_FORWARD) => {
+                self.widget.move_cursor(CursorMove::WordForward);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_TO_PARENT_INDENT) => {
+                let row = indent::parent_row(
+                    self.widget.lines(),
+                    self.widget.cursor().0,
+                    self.widget.tab_length(),
+                );
+                self.jump_to_indent(row);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING) => {
+                let row = indent::next_sibling_row(
+                    self.widget.lines(),
+                    self.widget.cursor().0,
+                    self.widget.tab_length(),
+                );
+                self.jump_to_indent(row);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_BOTTOM) => {
+                if !self.single_line {
+                    self.widget.move_cursor(CursorMove::Bottom);
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_TOP) => {
+                if !self.single_line {
+                    self.widget.move_cursor(CursorMove::Top);
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET) => {
+                if let Some((row, col)) =
+                    bracket::matching_bracket(self.widget.lines(), self.widget.cursor())
+                {
+                    self.widget.move_cursor(CursorMove::Jump(
+                        row.min(u16::MAX as usize) as u16,
+                        col.min(u16::MAX as usize) as u16,
+                    ));
+                }
+                CmdResult::None
+            }
+            #[cfg(feature = "clipboard")]
+            Cmd::Custom(TEXTAREA_CMD_COPY) if !self.is_masked() => {
+                self.copy();
+                CmdResult::None
+            }
+            #[cfg(feature = "clipboard")]
+            Cmd::Custom(TEXTAREA_CMD_CUT) if !self.is_masked() => {
+                self.edit_near_cursor(|this| this.cut());
+                CmdResult::None
+            }
+            #[cfg(feature = "clipboard")]
+            Cmd::Custom(TEXTAREA_CMD_PASTE) => {
+                self.edit_near_cursor(|this| this.paste());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_YANK_TO_REGISTER) => {
+                self.yank_to_register();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_PASTE_FROM_REGISTER) => {
+                if let Some(text) = self.registers.get(&self.register_name()).cloned() {
+                    self.insert_str(&text);
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_CLICK) => {
+                self.click();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DRAG) => {
+                self.drag();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DRAG_END) => {
+                self.drag_anchor = None;
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_SCROLL_MOUSE) => {
+                self.scroll_mouse();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_SET_CURSOR) => {
+                if let Some(AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Usize(row),
+                    PropValue::Usize(col),
+                )))) = self.query(Attribute::Custom(TEXTAREA_CURSOR_POSITION))
+                {
+                    self.jump((row, col));
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_GOTO_LINE) => {
+                if let Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(line)))) =
+                    self.query(Attribute::Custom(TEXTAREA_GOTO_LINE))
+                {
+                    // line numbers start from 1, while rows from 0
+                    self.jump((line.saturating_sub(1), 0));
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_SELECT_START) => {
+                self.widget.start_selection();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_SELECT_ALL) => {
+                let last_row = self.widget.lines().len() - 1;
+                self.select((0, 0), (last_row, usize::MAX));
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_SELECT_CANCEL) => {
+                self.widget.cancel_selection();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_TO_BOTTOM) => {
+                let (row, _) = self.widget.cursor();
+                let len = self.widget.lines().len();
+                self.record(row..len, false, |this| {
+                    this.delete_to((len - 1, usize::MAX))
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEL_TO_TOP) => {
+                let (row, _) = self.widget.cursor();
+                self.record(0..row + 1, false, |this| this.delete_to((0, 0)));
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DELETE_SELECTION) => {
+                // deleting a char deletes the selection instead, if any
+                if self.widget.selection_range().is_some() {
+                    self.edit_near_cursor(|this| {
+                        this.widget.delete_char();
+                    });
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_EXPAND_TABS) => {
+                self.edit(|this| this.expand_tabs());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TABIFY) => {
+                self.edit(|this| this.tabify());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_JOIN_LINES) => {
+                self.edit_near_cursor(|this| this.join_lines());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TRANSPOSE_CHARS) => {
+                self.edit_near_cursor(|this| this.transpose_chars());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_UPPERCASE) => {
+                self.change_case(|ch| ch.to_uppercase().collect());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_LOWERCASE) => {
+                self.change_case(|ch| ch.to_lowercase().collect());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TOGGLE_CASE) => {
+                self.change_case(|ch| match ch.is_uppercase() {
+                    true => ch.to_lowercase().collect(),
+                    false => ch.to_uppercase().collect(),
+                });
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DUPLICATE_SELECTION) => {
+                self.edit_near_cursor(|this| this.duplicate_selection());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE) => {
+                if !self.single_line {
+                    self.edit_near_cursor(|this| this.duplicate_line());
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_UP) => {
+                self.edit_near_cursor(|this| this.move_line(true));
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_DOWN) => {
+                self.edit_near_cursor(|this| this.move_line(false));
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_RETAB_SELECTION) => {
+                self.retab_selection();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_INDENT) => {
+                self.indent_selection();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_DEDENT) => {
+                self.dedent_selection();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TRIM_TRAILING_WHITESPACE) => {
+                self.edit(|this| this.trim_trailing_whitespace());
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_INSERT_STR) => {
+                if let Some(AttrValue::String(text)) =
+                    self.query(Attribute::Custom(TEXTAREA_INSERT_STR))
+                {
+                    self.insert_str(&text);
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT) => {
+                self.toggle_block_comment();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_TOGGLE_COMMENT) => {
+                self.toggle_comment();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_CLEAR) => {
+                self.clear();
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_REDO) => {
+                self.redo();
+                CmdResult::None
+            }
+            #[cfg(feature = "search")]
+            Cmd::Custom(TEXTAREA_CMD_REPLACE_NEXT) => {
+                self.edit(|this| this.replace_next());
+                CmdResult::None
+            }
+            #[cfg(feature = "search")]
+            Cmd::Custom(TEXTAREA_CMD_REPLACE_ALL) => {
+                self.edit(|this| this.replace_all());
+                CmdResult::None
+            }
+            #[cfg(feature = "search")]
+            Cmd::Custom(TEXTAREA_CMD_SEARCH_BACK) => {
+                self.search(false);
+                CmdResult::None
+            }
+            #[cfg(feature = "search")]
+            Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD) => {
+                self.search(true);
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_UNDO) => {
+                self.undo();
+                CmdResult::None
+            }
+            Cmd::Delete => {
+                self.edit_near_cursor(|this| this.delete_char());
+                CmdResult::None
+            }
+            Cmd::GoTo(Position::Begin) => {
+                self.widget.move_cursor(CursorMove::Head);
+                CmdResult::None
+            }
+            Cmd::GoTo(Position::End) => {
+                self.widget.move_cursor(CursorMove::End);
+                CmdResult::None
+            }
+            Cmd::Move(Direction::Down) => {
+                if !self.single_line {
+                    self.widget.move_cursor(CursorMove::Down);
+                }
+                CmdResult::None
+            }
+            Cmd::Move(Direction::Left) => {
+                self.widget.move_cursor(CursorMove::Back);
+                CmdResult::None
+            }
+            Cmd::Move(Direction::Right) => {
+                self.widget.move_cursor(CursorMove::Forward);
+                CmdResult::None
+            }
+            Cmd::Move(Direction::Up) => {
+                if !self.single_line {
+                    self.widget.move_cursor(CursorMove::Up);
+                }
+                CmdResult::None
+            }
+            Cmd::Scroll(Direction::Down) => {
+                if !self.single_line {
+                    let step = self
+                        .props
+                        .get_or(Attribute::ScrollStep, AttrValue::Length(8))
+                        .unwrap_length();
+                    (0..step).for_each(|_| self.widget.move_cursor(CursorMove::Down));
+                }
+                CmdResult::None
+            }
+            Cmd::Scroll(Direction::Up) => {
+                if !self.single_line {
+                    let step = self
+                        .props
+                        .get_or(Attribute::ScrollStep, AttrValue::Length(8))
+                        .unwrap_length();
+                    (0..step).for_each(|_| self.widget.move_cursor(CursorMove::Up));
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_PAGE_UP) => {
+                if !self.single_line {
+                    self.scroll_page(Direction::Up);
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN) => {
+                if !self.single_line {
+                    self.scroll_page(Direction::Down);
+                }
+                CmdResult::None
+            }
+            Cmd::Type('\t') => {
+                let smart = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_SMART_TAB),
+                        AttrValue::Flag(false),
+                    )
+                    .unwrap_flag();
+                let (row, col) = self.widget.cursor();
+                let in_indent = self.widget.lines()[row]
+                    .chars()
+                    .take(col)
+                    .all(char::is_whitespace);
+                if smart && (in_indent || self.widget.selection_range().is_some()) {
+                    self.indent_selection();
+                } else {
+                    self.edit_near_cursor(|this| {
+                        this.widget.insert_tab();
+                    });
+                }
+                CmdResult::None
+            }
+            Cmd::Custom(TEXTAREA_CMD_INSERT_HARD_TAB) => {
+                self.edit_near_cursor(|this| this.widget.insert_char('\t'));
+                CmdResult::None
+            }
+            Cmd::Type('\n') | Cmd::Custom(TEXTAREA_CMD_NEWLINE) => {
+                let submits = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_ENTER_SUBMITS),
+                        AttrValue::Flag(false),
+                    )
+                    .unwrap_flag();
+                if !self.single_line {
+                    self.edit_near_cursor(|this| this.widget.insert_newline());
+                } else if submits {
+                    return CmdResult::Submit(self.state());
+                }
+                CmdResult::None
+            }
+            Cmd::Type(ch) => {
+                let typing = self
+                    .props
+                    .get_or(
+                        Attribute::Custom(TEXTAREA_UNDO_COALESCE),
+                        AttrValue::Flag(false),
+                    )
+                    .unwrap_flag();
+                // a word typed after a whitespace starts a new step, so that words are undone one by one
+                if !ch.is_whitespace()
+                    && self
+                        .chars_around_cursor()
+                        .0
+                        .is_some_and(char::is_whitespace)
+                {
+                    self.history.break_typing();
+                }
+                let rows = self.rows_near_cursor();
+                self.record(rows, typing, |this| this.type_char(ch));
+                CmdResult::None
+            }
+            Cmd::Submit => CmdResult::Submit(self.state()),
+            _ => CmdResult::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use tuirealm::props::Color;
+    use tuirealm::ratatui::backend::TestBackend;
+    use tuirealm::ratatui::Terminal;
+
+    #[test]
+    fn should_undo_and_redo_changes() {
+        let mut component = TextArea::new(lines(&["hello"]));
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Type('!'));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Type('a'));
+        assert_eq!(component.widget.lines(), &lines(&["hello!", "a"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["hello!"]));
+        assert_eq!(component.widget.cursor(), (0, 6));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REDO));
+        assert_eq!(component.widget.lines(), &lines(&["hello!", ""]));
+        assert_eq!(component.widget.cursor(), (1, 0));
+    }
+
+    #[test]
+    fn should_record_edits_near_cursor() {
+        let mut component = TextArea::new(lines(&["a", "bc", "d", "e"])).cursor_at(1, 0);
+        // join with the line above
+        component.perform(Cmd::Delete);
+        assert_eq!(component.widget.lines(), &lines(&["abc", "d", "e"]));
+        // join with the line below
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Cancel);
+        assert_eq!(component.widget.lines(), &lines(&["abcd", "e"]));
+        // type over a selection across lines
+        component.select((0, 1), (1, 1));
+        component.perform(Cmd::Type('x'));
+        assert_eq!(component.widget.lines(), &lines(&["ax"]));
+        let edit = component.history.undo().unwrap();
+        assert_eq!(edit.row, 0);
+        assert_eq!(edit.before, lines(&["abcd", "e"]));
+        assert_eq!(edit.after, lines(&["ax"]));
+        component.history.redo();
+        for _ in 0..3 {
+            component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        }
+        assert_eq!(component.widget.lines(), &lines(&["a", "bc", "d", "e"]));
+    }
+
+    #[test]
+    fn should_undo_beyond_u16_positions() {
+        let mut text: Vec<String> = (0..70000).map(|x| x.to_string()).collect();
+        text.push("x".repeat(70000));
+        let mut component = TextArea::new(text.clone());
+        component.jump((69000, 2));
+        assert_eq!(component.cursor(), (69000, 2));
+        component.perform(Cmd::Type('!'));
+        assert_eq!(component.widget.lines()[69000], "69!000");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_UP));
+        assert_eq!(component.widget.lines()[68999], "69!000");
+        // the swapped lines are recorded
+        let edit = component.history.undo().unwrap();
+        assert_eq!(edit.row, 68999);
+        assert_eq!(edit.before.len(), 2);
+        component.history.redo();
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), text.as_slice());
+        assert_eq!(component.cursor(), (69000, 2));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REDO));
+        assert_eq!(component.widget.lines()[69000], "69!000");
+        assert_eq!(component.cursor(), (69000, 3));
+        // columns beyond u16 too
+        component.jump((70000, 69000));
+        component.perform(Cmd::Type('!'));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines()[70000], text[70000]);
+        assert_eq!(component.cursor(), (70000, 69000));
+    }
+
+    #[test]
+    fn should_tell_whether_can_undo_and_redo() {
+        let mut component = TextArea::new(lines(&["hello"]));
+        assert!(!component.can_undo());
+        assert!(!component.can_redo());
+        component.perform(Cmd::Type('!'));
+        assert!(component.can_undo());
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert!(!component.can_undo());
+        assert!(component.can_redo());
+        let mut component = TextArea::new(lines(&["hello"])).max_histories(0);
+        component.perform(Cmd::Type('!'));
+        assert!(!component.can_undo());
+    }
+
+    #[test]
+    fn should_indent_with_smart_tab() {
+        let mut component = TextArea::new(lines(&["  foo", "bar"]))
+            .smart_tab(true)
+            .cursor_at(0, 1);
+        component.perform(Cmd::Type('\t'));
+        assert_eq!(component.widget.lines(), &lines(&["      foo", "bar"]));
+        assert_eq!(component.cursor(), (0, 1));
+        // a tab is inserted after the indentation
+        component.widget.move_cursor(CursorMove::Jump(1, 1));
+        component.perform(Cmd::Type('\t'));
+        assert_eq!(component.widget.lines(), &lines(&["      foo", "b   ar"]));
+        // the selected lines are indented
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_ALL));
+        component.perform(Cmd::Type('\t'));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["          foo", "    b   ar"])
+        );
+    }
+
+    #[test]
+    fn should_insert_hard_tab() {
+        let mut component = TextArea::new(lines(&["ab"]))
+            .hard_tab(false)
+            .smart_tab(true)
+            .cursor_at(0, 0);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INSERT_HARD_TAB));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INSERT_HARD_TAB));
+        assert_eq!(component.text(), "\ta\tb");
+        let mut component = TextArea::new(lines(&["ab"])).read_only(true);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INSERT_HARD_TAB));
+        assert_eq!(component.text(), "ab");
+    }
+
+    #[test]
+    fn should_indent_and_dedent_lines() {
+        let mut component = TextArea::new(lines(&["foo", "\tbar", "baz"]))
+            .tab_length(2)
+            .hard_tab(false);
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INDENT));
+        assert_eq!(component.widget.lines(), &lines(&["  foo", "\tbar", "baz"]));
+        assert_eq!(component.widget.cursor(), (0, 3));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEDENT));
+        assert_eq!(component.widget.lines(), &lines(&["foo", "bar", "baz"]));
+        assert_eq!(component.widget.selection_range(), Some(((0, 1), (1, 2))));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEDENT));
+        assert_eq!(component.widget.lines(), &lines(&["foo", "bar", "baz"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["  foo", "\tbar", "baz"]));
+        let mut component = TextArea::new(lines(&["foo"])).hard_tab(true);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INDENT));
+        assert_eq!(component.widget.lines(), &lines(&["\tfoo"]));
+    }
+
+    #[test]
+    fn should_toggle_comment() {
+        let mut component = TextArea::new(lines(&["foo", "  // bar", "baz"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_COMMENT));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["foo", "  // bar", "baz"])
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_COMMENT_PREFIX),
+            AttrValue::String(String::from("// ")),
+        );
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_COMMENT));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["// foo", "  bar", "baz"])
+        );
+        assert_eq!(component.widget.selection_range(), Some(((0, 4), (1, 1))));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["foo", "  // bar", "baz"])
+        );
+    }
+
+    #[test]
+    fn should_toggle_block_comment() {
+        let mut component = TextArea::new(lines(&["a { color: red; }", "  b {}"]))
+            .block_comment("/* ", " */")
+            .cursor_at(1, 3);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT));
+        assert_eq!(component.widget.lines()[1], "  /* b {} */");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT));
+        assert_eq!(component.widget.lines()[1], "  b {}");
+        // the selection is wrapped and kept selected
+        component.widget.move_cursor(CursorMove::Jump(0, 4));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.widget.move_cursor(CursorMove::Jump(0, 15));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT));
+        assert_eq!(component.widget.lines()[0], "a { /* color: red; */ }");
+        assert_eq!(
+            component.selected_text().as_deref(),
+            Some("/* color: red; */")
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_BLOCK_COMMENT));
+        assert_eq!(component.widget.lines()[0], "a { color: red; }");
+        assert_eq!(component.selected_text().as_deref(), Some("color: red;"));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines()[0], "a { /* color: red; */ }");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines()[0], "a { color: red; }");
+    }
+
+    #[test]
+    fn should_register_fmt_key() {
+        let mut component =
+            TextArea::new(lines(&["hello"])).status_bar("{MODE} {ROW}", Style::default());
+        let status = |component: &TextArea| {
+            component
+                .status_fmt
+                .as_ref()
+                .unwrap()
+                .fmt(&component.widget)
+        };
+        assert_eq!(status(&component), " 1");
+        component.register_fmt_key("MODE", Arc::new(|_| String::from("INSERT")));
+        assert_eq!(status(&component), "INSERT 1");
+    }
+
+    #[test]
+    fn should_center_content_in_max_width() {
+        let mut component = TextArea::new(lines(&["hello", "world"]))
+            .max_width(6)
+            .status_bar("{ROW}", Style::default());
+        assert_eq!(
+            draw(&mut component, 10, 3),
+            vec!["  hello   ", "  world   ", "  1       "]
+        );
+        assert_eq!(component.area, Rect::new(2, 0, 6, 2));
+        component.attr(
+            Attribute::Custom(TEXTAREA_CLICK_POSITION),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Usize(4),
+                PropValue::Usize(1),
+            ))),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CLICK));
+        assert_eq!(component.cursor(), (1, 2));
+        assert_eq!(draw(&mut component, 4, 3)[0], "hell");
+    }
+
+    #[test]
+    fn should_render_scrollbar() {
+        let mut component = TextArea::new((0..10).map(|x| x.to_string()).collect())
+            .scrollbar(true, Style::default());
+        let bar = |rows: Vec<String>| -> String {
+            rows.iter().map(|row| row.chars().last().unwrap()).collect()
+        };
+        assert_eq!(bar(draw(&mut component, 4, 3)), "█║║");
+        assert_eq!(component.area, Rect::new(0, 0, 3, 3));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_BOTTOM));
+        assert_eq!(bar(draw(&mut component, 4, 3)), "║║█");
+        // hidden while the text fits
+        assert_eq!(bar(draw(&mut component, 4, 10)), " ".repeat(10));
+        assert_eq!(component.area, Rect::new(0, 0, 4, 10));
+        // drawn over the right border
+        let mut component = component.borders(Borders::default());
+        assert_eq!(
+            draw(&mut component, 7, 7),
+            vec![
+                "       ",
+                " ┌───┐ ",
+                " │7  ║ ",
+                " │8  ║ ",
+                " │9  █ ",
+                " └───┘ ",
+                "       "
+            ]
+        );
+        assert_eq!(component.area, Rect::new(1, 1, 5, 5));
+    }
+
+    #[test]
+    fn should_move_cursor_on_click() {
+        let mut component = TextArea::new(lines(&["hello", "world"]))
+            .borders(Borders::default())
+            .line_number_style(Style::default());
+        component.area = Rect::new(0, 0, 12, 4);
+        let click = |component: &mut TextArea, x: usize, y: usize| {
+            component.attr(
+                Attribute::Custom(TEXTAREA_CLICK_POSITION),
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Usize(x),
+                    PropValue::Usize(y),
+                ))),
+            );
+            component.perform(Cmd::Custom(TEXTAREA_CMD_CLICK));
+            component.widget.cursor()
+        };
+        assert_eq!(click(&mut component, 6, 2), (1, 2));
+        assert_eq!(click(&mut component, 10, 1), (0, 5));
+        // border and gutter
+        assert_eq!(click(&mut component, 0, 1), (0, 5));
+        assert_eq!(click(&mut component, 2, 2), (0, 5));
+    }
+
+    #[test]
+    fn should_select_on_drag() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.area = Rect::new(0, 0, 10, 2);
+        let mouse = |component: &mut TextArea, cmd: &'static str, x: usize, y: usize| {
+            component.attr(
+                Attribute::Custom(TEXTAREA_CLICK_POSITION),
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Usize(x),
+                    PropValue::Usize(y),
+                ))),
+            );
+            component.perform(Cmd::Custom(cmd));
+        };
+        mouse(&mut component, TEXTAREA_CMD_CLICK, 1, 0);
+        mouse(&mut component, TEXTAREA_CMD_DRAG, 3, 0);
+        mouse(&mut component, TEXTAREA_CMD_DRAG, 2, 1);
+        assert_eq!(component.selected_text().unwrap(), "ello\nwo");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DRAG_END));
+        assert_eq!(component.selected_text().unwrap(), "ello\nwo");
+        // a new drag starts from the cursor
+        mouse(&mut component, TEXTAREA_CMD_DRAG, 4, 1);
+        assert_eq!(component.selected_text().unwrap(), "rl");
+        mouse(&mut component, TEXTAREA_CMD_CLICK, 0, 0);
+        assert_eq!(component.selected_text(), None);
+    }
+
+    #[test]
+    fn should_scroll_viewport_with_mouse() {
+        let mut component = TextArea::new((0..10).map(|x| x.to_string()).collect()).scroll_step(3);
+        component.area = Rect::new(0, 0, 10, 4);
+        let scroll = |component: &mut TextArea, delta: isize| {
+            component.attr(
+                Attribute::Custom(TEXTAREA_SCROLL_DELTA),
+                AttrValue::Payload(PropPayload::One(PropValue::Isize(delta))),
+            );
+            component.perform(Cmd::Custom(TEXTAREA_CMD_SCROLL_MOUSE));
+            component.viewport.row
+        };
+        assert_eq!(scroll(&mut component, 1), 3);
+        assert_eq!(scroll(&mut component, 2), 6);
+        assert!(!component.follow_cursor);
+        assert_eq!(component.widget.cursor(), (0, 0));
+        assert_eq!(scroll(&mut component, -1), 3);
+        assert_eq!(scroll(&mut component, -2), 0);
+        component.perform(Cmd::Move(Direction::Down));
+        assert!(component.follow_cursor);
+    }
+
+    #[test]
+    fn should_highlight_word_at_cursor() {
+        let style = Style::default().bg(Color::DarkGray);
+        let mut component = TextArea::new(lines(&["let foo = 1;", "foo_bar(foo)"]))
+            .highlight_word(true, style)
+            .cursor_at(0, 3);
+        assert_eq!(component.word_highlights(2), vec![]);
+        component.perform(Cmd::Move(Direction::Right));
+        assert_eq!(
+            component.word_highlights(2),
+            vec![
+                ((0, 4), style),
+                ((0, 5), style),
+                ((0, 6), style),
+                ((1, 8), style),
+                ((1, 9), style),
+                ((1, 10), style),
+            ]
+        );
+        component.perform(Cmd::Move(Direction::Left));
+        assert_eq!(component.word_highlights(2), vec![]);
+        // the masked text isn't exposed
+        let mut component = component.mask_char('*');
+        component.perform(Cmd::Move(Direction::Right));
+        assert_eq!(component.word_highlights(2), vec![]);
+        let mut component = component
+            .highlight_word(false, style)
+            .match_brackets(true)
+            .cursor_at(1, 7);
+        assert_eq!(component.bracket_highlights(), vec![]);
+        component.perform(Cmd::Move(Direction::Right));
+        assert_eq!(component.word_highlights(2), vec![]);
+    }
+
+    #[test]
+    fn should_transpose_chars() {
+        let mut component = TextArea::new(lines(&["aé日b"])).cursor_at(0, 2);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TRANSPOSE_CHARS));
+        assert_eq!(component.text(), "a日éb");
+        assert_eq!(component.cursor(), (0, 3));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TRANSPOSE_CHARS));
+        assert_eq!(component.text(), "a日bé");
+        assert_eq!(component.cursor(), (0, 4));
+        // at the end of the line, the last two chars are swapped
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TRANSPOSE_CHARS));
+        assert_eq!(component.text(), "a日éb");
+        assert_eq!(component.cursor(), (0, 4));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "a日bé");
+        component.perform(Cmd::GoTo(Position::Begin));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TRANSPOSE_CHARS));
+        assert_eq!(component.text(), "a日bé");
+        assert_eq!(component.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn should_change_case_of_word() {
+        let mut component = TextArea::new(lines(&["let straße = 1;"])).cursor_at(0, 9);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UPPERCASE));
+        assert_eq!(component.text(), "let STRASSE = 1;");
+        assert_eq!(component.cursor(), (0, 10));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "let straße = 1;");
+        component.perform(Cmd::GoTo(Position::Begin));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_CASE));
+        assert_eq!(component.text(), "LET straße = 1;");
+        assert_eq!(component.cursor(), (0, 0));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_LOWERCASE));
+        assert_eq!(component.text(), "LET straße = 1;");
+    }
+
+    #[test]
+    fn should_change_case_of_selection() {
+        let mut component = TextArea::new(lines(&["hello wörld", "foo Bar"])).cursor_at(1, 5);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Up));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UPPERCASE));
+        assert_eq!(component.text(), "hello WÖRLD\nFOO Bar");
+        assert_eq!(component.widget.selection_range(), Some(((0, 5), (1, 5))));
+        assert_eq!(component.cursor(), (0, 5));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TOGGLE_CASE));
+        assert_eq!(component.text(), "hello wörld\nfoo bar");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello WÖRLD\nFOO Bar");
+    }
+
+    #[test]
+    fn should_make_textarea_from_reader() {
+        let component = TextArea::from_reader("hello\r\nworld\n".as_bytes()).unwrap();
+        assert_eq!(component.text(), "hello\nworld");
+        let component = TextArea::from_reader(&b""[..]).unwrap();
+        assert_eq!(component.text(), "");
+        assert!(TextArea::from_reader(&b"\xff\n"[..]).is_err());
+    }
+
+    #[test]
+    fn should_move_cursor_on_focus() {
+        let mut component = TextArea::new(lines(&["hello", "world"])).cursor_at(0, 2);
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(component.cursor(), (0, 2));
+        let mut component = component.focus_cursor_behavior(FocusCursorBehavior::ToEnd);
+        component.perform(Cmd::Move(Direction::Left));
+        // only gaining focus moves the cursor
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(component.cursor(), (0, 1));
+        component.attr(Attribute::Focus, AttrValue::Flag(false));
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(component.cursor(), (1, 5));
+        let mut component = component.focus_cursor_behavior(FocusCursorBehavior::ToStart);
+        component.attr(Attribute::Focus, AttrValue::Flag(false));
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(component.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn should_delete_to_bottom_and_top() {
+        let mut component = TextArea::new(lines(&["hello", "wörld", "!"])).cursor_at(1, 2);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEL_TO_BOTTOM));
+        assert_eq!(component.text(), "hello\nwö");
+        assert_eq!(component.cursor(), (1, 2));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEL_TO_BOTTOM));
+        assert_eq!(component.text(), "hello\nwö");
+        assert_eq!(component.cursor(), (1, 2));
+        component.perform(Cmd::Move(Direction::Left));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEL_TO_TOP));
+        assert_eq!(component.text(), "ö");
+        assert_eq!(component.cursor(), (0, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEL_TO_TOP));
+        assert_eq!(component.text(), "ö");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nwö");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nwörld\n!");
+    }
+
+    #[test]
+    fn should_reject_newlines_in_single_line_mode() {
+        let mut component = TextArea::new(lines(&["foo"]));
+        assert!(!component.is_single_line());
+        component.attr(
+            Attribute::Custom(TEXTAREA_SINGLE_LINE),
+            AttrValue::Flag(true),
+        );
+        assert!(component.is_single_line());
+        assert_eq!(
+            component.query(Attribute::Custom(TEXTAREA_SINGLE_LINE)),
+            Some(AttrValue::Flag(true))
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Type('\n'));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.widget.lines(), &lines(&["foo"]));
+        let component = component.single_line(false);
+        assert!(!component.is_single_line());
+    }
+
+    #[test]
+    fn should_submit_on_enter_in_single_line_mode() {
+        let mut component = TextArea::new(lines(&["foo"]))
+            .single_line(true)
+            .enter_submits(true);
+        assert_eq!(
+            component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE)),
+            CmdResult::Submit(State::Vec(vec![StateValue::String(String::from("foo"))]))
+        );
+        assert_eq!(
+            component.perform(Cmd::Type('\n')),
+            CmdResult::Submit(State::Vec(vec![StateValue::String(String::from("foo"))]))
+        );
+        assert_eq!(component.text(), "foo");
+        let mut component = TextArea::new(lines(&["foo"])).enter_submits(true);
+        assert_eq!(
+            component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE)),
+            CmdResult::None
+        );
+        assert_eq!(component.text(), "\nfoo");
+    }
+
+    #[test]
+    fn should_yank_and_paste_registers() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PASTE_FROM_REGISTER));
+        assert_eq!(component.text(), "hello\nworld");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_YANK_TO_REGISTER));
+        assert_eq!(component.register('"'), Some("hello\n"));
+        component.attr(
+            Attribute::Custom(TEXTAREA_REGISTER),
+            AttrValue::String(String::from("a")),
+        );
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_YANK_TO_REGISTER));
+        assert_eq!(component.register('a'), Some("wo"));
+        assert_eq!(component.register('"'), Some("wo"));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_CANCEL));
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PASTE_FROM_REGISTER));
+        assert_eq!(component.text(), "hello\nworldwo");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nworld");
+        assert_eq!(component.register('b'), None);
+    }
+
+    #[test]
+    fn should_draw_rulers() {
+        let mut component = TextArea::new(lines(&["hello"]));
+        component.attr(Attribute::Custom(TEXTAREA_RULER_COLUMN), AttrValue::Size(3));
+        assert_eq!(component.ruler_columns(), vec![2]);
+        assert_eq!(draw(&mut component, 6, 2), vec!["hello ", "  │   "]);
+        let mut component = component.rulers(&[0, 2, 5], Style::default());
+        assert_eq!(component.ruler_columns(), vec![1, 4]);
+        assert_eq!(draw(&mut component, 6, 2), vec!["hello ", " │  │ "]);
+    }
+
+    #[test]
+    fn should_style_unfocused_cursor() {
+        let cursor_fg = |component: &mut TextArea| {
+            let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+            terminal
+                .draw(|frame| component.view(frame, frame.area()))
+                .unwrap();
+            terminal.backend().buffer()[(0, 0)].fg
+        };
+        let mut component = TextArea::new(lines(&["foo"]))
+            .cursor_style(Style::default().fg(Color::Red))
+            .unfocused_cursor_style(Style::default().fg(Color::DarkGray));
+        assert_eq!(cursor_fg(&mut component), Color::DarkGray);
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(cursor_fg(&mut component), Color::Red);
+        component.attr(Attribute::Focus, AttrValue::Flag(false));
+        assert_eq!(cursor_fg(&mut component), Color::DarkGray);
+    }
+
+    #[test]
+    fn should_append_text_and_lines() {
+        let mut component = TextArea::default();
+        component.append_line("foo");
+        component.append_line("bar");
+        assert_eq!(component.text(), "foo\nbar");
+        assert_eq!(component.cursor(), (1, 3));
+        component.append_text("baz\nqux");
+        assert_eq!(component.text(), "foo\nbarbaz\nqux");
+        assert_eq!(component.cursor(), (2, 3));
+        // cursor not at the end is left in place
+        component.perform(Cmd::Move(Direction::Up));
+        component.append_line("quux");
+        assert_eq!(component.text(), "foo\nbarbaz\nqux\nquux");
+        assert_eq!(component.cursor(), (1, 3));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "foo\nbarbaz\nqux");
+    }
+
+    #[test]
+    fn should_paste_str_and_set_yank() {
+        let mut component = TextArea::new(lines(&["hello"])).cursor_at(0, 5);
+        component.paste_str(" big\r\nworld");
+        assert_eq!(component.text(), "hello big\nworld");
+        assert_eq!(component.cursor(), (1, 5));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello");
+        component.set_yank(String::from("foo"));
+        assert_eq!(component.widget.yank_text(), "foo");
+    }
+
+    #[test]
+    fn should_set_line() {
+        let mut component = TextArea::new(lines(&["hello", "world"])).cursor_at(1, 4);
+        component.set_line(1, "you\nignored");
+        assert_eq!(component.text(), "hello\nyou");
+        assert_eq!(component.cursor(), (1, 3));
+        component.set_line(0, "hi");
+        assert_eq!(component.text(), "hi\nyou");
+        assert_eq!(component.cursor(), (1, 3));
+        component.set_line(2, "out of range");
+        assert_eq!(component.text(), "hi\nyou");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nyou");
+    }
+
+    #[test]
+    fn should_duplicate_selection() {
+        let mut component = TextArea::new(lines(&["foo bar", "baz"])).cursor_at(0, 4);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.widget.move_cursor(CursorMove::Jump(1, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_SELECTION));
+        assert_eq!(component.text(), "foo bar\nbbar\nbaz");
+        assert_eq!(component.selected_text().as_deref(), Some("bar\nb"));
+        assert_eq!(component.cursor(), (2, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "foo bar\nbaz");
+        // without a selection the line is duplicated
+        component.widget.cancel_selection();
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_SELECTION));
+        assert_eq!(component.text(), "foo bar\nbaz\nbaz");
+    }
+
+    #[test]
+    fn should_clear_text() {
+        let mut component = TextArea::new(lines(&["hello", "world"]))
+            .cursor_style(Style::default().fg(Color::Red))
+            .cursor_at(1, 2);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CLEAR));
+        assert_eq!(component.text(), "");
+        assert_eq!(component.cursor(), (0, 0));
+        assert_eq!(
+            component.widget.cursor_style(),
+            Style::default().fg(Color::Red)
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nworld");
+        let mut component = TextArea::new(lines(&["foo"])).read_only(true);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CLEAR));
+        assert_eq!(component.text(), "foo");
+    }
+
+    #[test]
+    fn should_build_with_cursor_at() {
+        let mut component =
+            TextArea::new((0..10).map(|x| format!("line {x}")).collect()).cursor_at(7, 5);
+        assert_eq!(component.cursor(), (7, 5));
+        assert_eq!(
+            draw(&mut component, 6, 3),
+            vec!["line 5", "line 6", "line 7"]
+        );
+        let component = TextArea::new(lines(&["foo"])).cursor_at(4, 8);
+        assert_eq!(component.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn should_snapshot_and_restore() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Move(Direction::Right));
+        let snap = component.snapshot();
+        assert_eq!(snap.cursor, (1, 1));
+        let mut component = TextArea::new(lines(&[""]));
+        component.restore(snap);
+        assert_eq!(component.text(), "hello\nworld");
+        assert_eq!(component.cursor(), (1, 1));
+        assert!(!component.is_modified());
+        component.restore(TextAreaSnapshot {
+            lines: lines(&["foo"]),
+            cursor: (4, 8),
+        });
+        assert_eq!(component.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn should_scroll_by_page() {
+        let mut component = TextArea::new((0..20).map(|x| x.to_string()).collect()).scroll_step(2);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN));
+        assert_eq!(component.widget.cursor(), (2, 0));
+        let mut component = TextArea::new((0..20).map(|x| x.to_string()).collect());
+        assert_eq!(draw(&mut component, 4, 5)[0], "0   ");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN));
+        assert_eq!(component.widget.cursor(), (4, 0));
+        assert_eq!(draw(&mut component, 4, 5)[0], "4   ");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_DOWN));
+        assert_eq!(component.widget.cursor(), (16, 0));
+        assert_eq!(draw(&mut component, 4, 5)[0], "15  ");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_PAGE_UP));
+        assert_eq!(component.widget.cursor(), (12, 0));
+        assert_eq!(draw(&mut component, 4, 5)[0], "11  ");
+    }
+
+    #[test]
+    fn should_track_modified_text() {
+        let mut component = TextArea::new(lines(&["hello"]));
+        assert!(!component.is_modified());
+        component.perform(Cmd::Type('!'));
+        assert!(component.is_modified());
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert!(!component.is_modified());
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REDO));
+        component.reset_modified();
+        assert!(!component.is_modified());
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert!(component.is_modified());
+        component.set_lines(lines(&["world"]));
+        assert!(!component.is_modified());
+    }
+
+    #[test]
+    fn should_trim_trailing_whitespace() {
+        let mut component = TextArea::new(lines(&["foo  ", "\tbar\t \t", "   ", ""]));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TRIM_TRAILING_WHITESPACE));
+        assert_eq!(component.widget.lines(), &lines(&["foo", "\tbar", "", ""]));
+        assert_eq!(component.widget.cursor(), (1, 4));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["foo  ", "\tbar\t \t", "   ", ""])
+        );
+    }
+
+    #[test]
+    fn should_insert_str() {
+        let mut component = TextArea::new(lines(&["ab"]));
+        component.perform(Cmd::Move(Direction::Right));
+        component.insert_str("foo\r\nbar");
+        assert_eq!(component.widget.lines(), &lines(&["afoo", "barb"]));
+        assert_eq!(component.widget.cursor(), (1, 3));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["ab"]));
+        // by command
+        component.attr(
+            Attribute::Custom(TEXTAREA_INSERT_STR),
+            AttrValue::String(String::from("x\n")),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_INSERT_STR));
+        assert_eq!(component.widget.lines(), &lines(&["ax", "b"]));
+        // single line
+        let mut component = TextArea::new(lines(&[""])).single_line(true);
+        component.insert_str("foo\nbar");
+        assert_eq!(component.widget.lines(), &lines(&["foo"]));
+    }
+
+    #[test]
+    fn should_expand_tabs_and_tabify() {
+        let mut component = TextArea::new(lines(&["\tfoo\tbar", "  \tbaz"])).tab_length(4);
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_EXPAND_TABS));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["    foo bar", "    baz"])
+        );
+        assert_eq!(component.widget.cursor(), (0, 11));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_TABIFY));
+        assert_eq!(component.widget.lines(), &lines(&["\tfoo bar", "\tbaz"]));
+        assert_eq!(component.widget.cursor(), (1, 4));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["    foo bar", "    baz"])
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["\tfoo\tbar", "  \tbaz"]));
+    }
+
+    #[test]
+    fn should_retab_selection() {
+        let mut component = TextArea::new(lines(&["\tfoo", "  \tbar\t\"\t\"", "\tbaz"]))
+            .tab_length(4)
+            .hard_tab(false);
+        component.perform(Cmd::Move(Direction::Right));
+        component.widget.start_selection();
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_RETAB_SELECTION));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["    foo", "    bar\t\"\t\"", "\tbaz"])
+        );
+        assert_eq!(component.widget.selection_range(), Some(((0, 4), (1, 1))));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(
+            component.widget.lines(),
+            &lines(&["\tfoo", "  \tbar\t\"\t\"", "\tbaz"])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_center_search_match() {
+        let mut component =
+            TextArea::new((0..20).map(|x| format!("line {x}")).collect()).search_center(true);
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("15")),
+        );
+        assert_eq!(draw(&mut component, 8, 5)[0], "line 0  ");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD));
+        assert_eq!(component.cursor(), (15, 5));
+        assert_eq!(draw(&mut component, 8, 5)[0], "line 13 ");
+        // the view is not scrolled past the end
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("19")),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD));
+        assert_eq!(draw(&mut component, 8, 5)[0], "line 15 ");
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_search_with_options() {
+        let mut component =
+            TextArea::new(lines(&["Foo food", "foo"])).search_options(SearchOptions {
+                regex: false,
+                case_insensitive: true,
+                whole_word: true,
+                wrap: false,
+            });
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("foo")),
+        );
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD));
+        assert_eq!(component.widget.cursor(), (1, 0));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD));
+        assert_eq!(component.widget.cursor(), (1, 1));
+    }
+
+    #[test]
+    fn should_move_to_parent_indent_and_next_sibling() {
+        let mut component = TextArea::new(lines(&["a:", "  b:", "    c", "  d: 1", "e"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING));
+        assert_eq!(component.widget.cursor(), (4, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING));
+        assert_eq!(component.widget.cursor(), (4, 0));
+        component.perform(Cmd::Move(Direction::Up));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_TO_PARENT_INDENT));
+        assert_eq!(component.widget.cursor(), (0, 0));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_TO_NEXT_SIBLING));
+        assert_eq!(component.widget.cursor(), (3, 2));
+    }
+
+    #[test]
+    fn should_split_str_into_lines() {
+        let component = TextArea::from_str_lines("hello\r\nworld\n");
+        assert_eq!(component.widget.lines(), &lines(&["hello", "world", ""]));
+        let component = TextArea::from_str_lines("");
+        assert_eq!(component.widget.lines(), &lines(&[""]));
+    }
+
+    #[test]
+    fn should_get_lines() {
+        let component = TextArea::new(lines(&["hello", "world"]));
+        assert_eq!(component.line_count(), 2);
+        assert_eq!(component.line(1), Some("world"));
+        assert_eq!(component.line(2), None);
+        assert_eq!(TextArea::default().line_count(), 1);
+    }
+
+    #[test]
+    fn should_get_text() {
+        let component = TextArea::new(lines(&["hello", "", "world"]));
+        assert_eq!(component.text(), "hello\n\nworld");
+        assert_eq!(TextArea::default().text(), "");
+        let component = component.line_ending(LineEnding::CrLf);
+        assert_eq!(component.text(), "hello\r\n\r\nworld");
+    }
+
+    #[test]
+    fn should_trim_trailing_newline_from_text() {
+        let component = TextArea::new(lines(&["hello", "", ""]));
+        assert_eq!(component.text(), "hello\n\n");
+        let component = component.trim_trailing_newline(true);
+        assert_eq!(component.text(), "hello\n");
+        assert_eq!(component.widget.lines().len(), 3);
+        let component = TextArea::default().trim_trailing_newline(true);
+        assert_eq!(component.text(), "");
+    }
+
+    #[test]
+    fn should_set_lines() {
+        let mut component = TextArea::new(lines(&["hello"]))
+            .line_number_style(Style::default().fg(Color::Yellow))
+            .tab_length(2)
+            .mask_char('*')
+            .borders(Borders::default());
+        component.perform(Cmd::Type('!'));
+        component.drag_anchor = Some((0, 1));
+        component.set_lines(lines(&["foo", "bar"]));
+        assert_eq!(component.drag_anchor, None);
+        assert_eq!(component.widget.mask_char(), Some('*'));
+        assert!(component.widget.block().is_some());
+        assert_eq!(component.text(), "foo\nbar");
+        assert_eq!(component.widget.cursor(), (0, 0));
+        assert_eq!(
+            component.widget.line_number_style(),
+            Some(Style::default().fg(Color::Yellow))
+        );
+        assert_eq!(component.widget.tab_length(), 2);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "foo\nbar");
+    }
+
+    #[test]
+    fn should_reject_edits_when_read_only() {
+        let mut component = TextArea::new(lines(&["hello", "world"])).read_only(true);
+        component.perform(Cmd::Type('a'));
+        component.perform(Cmd::Cancel);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DEL_LINE_BY_END));
+        assert_eq!(component.text(), "hello\nworld");
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::GoTo(Position::End));
+        assert_eq!(component.widget.cursor(), (1, 5));
+        component.attr(Attribute::Custom(TEXTAREA_READONLY), AttrValue::Flag(false));
+        component.perform(Cmd::Type('!'));
+        assert_eq!(component.text(), "hello\nworld!");
+    }
+
+    #[test]
+    fn should_ignore_disabled_cmds() {
+        let mut component = TextArea::new(lines(&["hello"]))
+            .disabled_cmds(&[TEXTAREA_CMD_UNDO, TEXTAREA_CMD_DUPLICATE_LINE]);
+        component.perform(Cmd::Type('!'));
+        assert_eq!(component.text(), "!hello");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.text(), "!hello");
+        component.perform(Cmd::GoTo(Position::End));
+        assert_eq!(component.widget.cursor(), (0, 6));
+        component.attr(
+            Attribute::Custom(TEXTAREA_DISABLED_CMDS),
+            AttrValue::Payload(PropPayload::Vec(vec![])),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello");
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn should_copy_selection_or_line() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_COPY));
+        assert_eq!(component.widget.yank_text(), "hello\n");
+        component.select((0, 1), (1, 2));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_COPY));
+        assert_eq!(component.widget.yank_text(), "ello\nwo");
+        assert_eq!(component.text(), "hello\nworld");
+        // the masked text isn't exposed
+        let mut component = TextArea::new(lines(&["secret"])).mask_char('*');
+        component.perform(Cmd::Custom(TEXTAREA_CMD_COPY));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CUT));
+        assert_eq!(component.widget.yank_text(), "");
+        assert_eq!(component.text(), "secret");
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn should_cut_selection_or_line() {
+        let mut component = TextArea::new(lines(&["hello", "world", "!"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CUT));
+        assert_eq!(component.widget.yank_text(), "hello\n");
+        assert_eq!(component.text(), "world\n!");
+        component.select((0, 1), (1, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CUT));
+        assert_eq!(component.widget.yank_text(), "orld\n");
+        assert_eq!(component.text(), "w!");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\nworld\n!");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_BOTTOM));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_CUT));
+        assert_eq!(component.widget.yank_text(), "!\n");
+        assert_eq!(component.text(), "hello\nworld");
+    }
+
+    #[test]
+    fn should_extend_selection_while_moving() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::GoTo(Position::End));
+        assert_eq!(component.widget.selection_range(), Some(((0, 1), (1, 5))));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_CANCEL));
+        assert_eq!(component.widget.selection_range(), None);
+        component.perform(Cmd::Move(Direction::Left));
+        assert_eq!(component.widget.selection_range(), None);
+    }
+
+    #[test]
+    fn should_get_selected_text() {
+        let mut component = TextArea::new(lines(&["hello", "big", "world"]));
+        assert_eq!(component.selected_text(), None);
+        component.select((0, 3), (2, 2));
+        assert_eq!(component.selected_text().unwrap(), "lo\nbig\nwo");
+        component.select((1, 1), (1, 2));
+        assert_eq!(component.selected_text().unwrap(), "i");
+        component.select((1, 1), (1, 1));
+        assert_eq!(component.selected_text(), None);
+    }
+
+    #[test]
+    fn should_select_all() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_ALL));
+        assert_eq!(component.selected_text().unwrap(), "hello\nworld");
+        assert_eq!(component.widget.cursor(), (1, 5));
+        let mut component = TextArea::new(lines(&["hello"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_ALL));
+        assert_eq!(component.selected_text().unwrap(), "hello");
+        let mut component = TextArea::default();
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_ALL));
+        assert_eq!(component.selected_text(), None);
+    }
+
+    #[test]
+    fn should_wrap_bars_taller_than_a_row() {
+        let mut component = TextArea::new(lines(&["foo"]))
+            .footer_bar("Ln {ROW} of {LINES}", Style::default())
+            .footer_height(2);
+        assert_eq!(
+            draw(&mut component, 8, 3),
+            vec!["foo     ", "Ln 1 of ", "1       "]
+        );
+        let mut component =
+            TextArea::new(lines(&["foo"])).status_bar("Ln {ROW} of {LINES}", Style::default());
+        assert_eq!(draw(&mut component, 8, 2), vec!["foo     ", "Ln 1 of "]);
+    }
+
+    #[test]
+    fn should_show_line_numbers() {
+        let mut component = TextArea::new(lines(&["a"])).show_line_numbers(true);
+        assert_eq!(component.widget.line_number_style(), Some(Style::default()));
+        let style = Style::default().fg(Color::Yellow);
+        component.attr(
+            Attribute::Custom(TEXTAREA_LINE_NUMBER_STYLE),
+            AttrValue::Style(style),
+        );
+        assert_eq!(component.widget.line_number_style(), Some(style));
+        component.attr(
+            Attribute::Custom(TEXTAREA_SHOW_LINE_NUMBERS),
+            AttrValue::Flag(false),
+        );
+        assert_eq!(component.widget.line_number_style(), None);
+        assert_eq!(draw(&mut component, 2, 1), vec!["a "]);
+        component.attr(
+            Attribute::Custom(TEXTAREA_SHOW_LINE_NUMBERS),
+            AttrValue::Flag(true),
+        );
+        assert_eq!(component.widget.line_number_style(), Some(style));
+        // the current line number style is the cursor gutter style
+        component.attr(
+            Attribute::Custom(TEXTAREA_CURRENT_LINE_NUMBER_STYLE),
+            AttrValue::Style(style),
+        );
+        assert_eq!(
+            component.query(Attribute::Custom(TEXTAREA_CURSOR_GUTTER_STYLE)),
+            Some(AttrValue::Style(style))
+        );
+    }
+
+    #[test]
+    fn should_render_relative_line_numbers() {
+        let mut component = TextArea::new(lines(&["a", "b", "c"]))
+            .line_number_style(Style::default())
+            .relative_line_numbers(true);
+        assert_eq!(draw(&mut component, 4, 3), vec![" 1 a", " 1 b", " 2 c"]);
+        component.perform(Cmd::Move(Direction::Down));
+        assert_eq!(draw(&mut component, 4, 3), vec![" 1 a", " 2 b", " 1 c"]);
+    }
+
+    #[test]
+    fn should_style_borders_when_invalid() {
+        let mut component = TextArea::new(lines(&["foo"]))
+            .borders(Borders::default().color(Color::Blue))
+            .inactive(Style::default().fg(Color::Gray));
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        let border = |component: &mut TextArea| {
+            let mut terminal = Terminal::new(TestBackend::new(7, 5)).unwrap();
+            terminal
+                .draw(|frame| component.view(frame, frame.area()))
+                .unwrap();
+            terminal.backend().buffer()[(1, 1)].fg
+        };
+        assert_eq!(border(&mut component), Color::Blue);
+        component.mark_invalid(true);
+        assert!(component.is_invalid());
+        assert_eq!(border(&mut component), Color::Red);
+        let mut component = component.invalid_style(Style::default().fg(Color::Yellow));
+        assert_eq!(border(&mut component), Color::Yellow);
+        // the inactive style is kept while not focused
+        component.attr(Attribute::Focus, AttrValue::Flag(false));
+        assert_eq!(border(&mut component), Color::Gray);
+        component.mark_invalid(false);
+        component.attr(Attribute::Focus, AttrValue::Flag(true));
+        assert_eq!(border(&mut component), Color::Blue);
+    }
+
+    #[test]
+    fn should_mask_text() {
+        let mut component = TextArea::new(lines(&["pä日"]))
+            .single_line(true)
+            .mask_char('*');
+        assert_eq!(draw(&mut component, 5, 1), vec!["***  "]);
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Delete);
+        assert_eq!(draw(&mut component, 5, 1), vec!["**   "]);
+        assert_eq!(
+            component.state(),
+            State::Vec(vec![StateValue::String(String::from("pä"))])
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_MASK_CHAR),
+            AttrValue::String(String::new()),
+        );
+        assert_eq!(draw(&mut component, 5, 1), vec!["pä   "]);
+    }
+
+    #[test]
+    fn should_get_desired_height() {
+        let component = TextArea::new(lines(&["hello world", "foo"]));
+        assert_eq!(component.desired_height(6), 2);
+        let component = component.line_wrap(true);
+        assert_eq!(component.desired_height(6), 3);
+        let mut component = component
+            .borders(Borders::default())
+            .status_bar("{ROW}", Style::default());
+        // borders, with a margin of 1 around them
+        assert_eq!(component.desired_height(10), 3 + 2 + 2 + 1);
+        assert_eq!(draw(&mut component, 10, 8)[4], " │foo   │ ");
+        let component = component.max_visible_lines(2);
+        assert_eq!(component.desired_height(10), 2 + 2 + 2 + 1);
+        let component = TextArea::new(lines(&[""])).min_visible_lines(3);
+        assert_eq!(component.desired_height(10), 3);
+    }
+
+    #[test]
+    fn should_set_layout_margin() {
+        let mut component = TextArea::new(lines(&["foo"])).layout_margin(1);
+        assert_eq!(draw(&mut component, 5, 3), vec!["     ", " foo ", "     "]);
+        let mut component = TextArea::new(lines(&["foo"])).layout_margins(0, 1);
+        assert_eq!(draw(&mut component, 5, 3), vec!["     ", "foo  ", "     "]);
+    }
+
+    #[test]
+    fn should_get_and_set_viewport_offset() {
+        let mut component = TextArea::new((0..10).map(|x| format!("line {x}")).collect());
+        assert_eq!(component.viewport_offset(), (0, 0));
+        component.set_viewport_offset(4, 2);
+        assert_eq!(draw(&mut component, 4, 2), vec!["ne 4", "ne 5"]);
+        assert_eq!(component.viewport_offset(), (4, 2));
+        assert_eq!(component.cursor(), (0, 0));
+        component.set_viewport_offset(20, 0);
+        assert_eq!(component.viewport_offset(), (9, 0));
+        // the next command brings the cursor back into view
+        component.perform(Cmd::Move(Direction::Down));
+        draw(&mut component, 4, 2);
+        assert_eq!(component.viewport_offset(), (1, 0));
+    }
+
+    #[test]
+    fn should_move_to_matching_bracket() {
+        let mut component = TextArea::new(lines(&["f(a[0], {", "  (b)", "})", "("]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET));
+        assert_eq!(component.cursor(), (0, 0));
+        component.widget.move_cursor(CursorMove::Forward);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET));
+        assert_eq!(component.cursor(), (2, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET));
+        assert_eq!(component.cursor(), (0, 1));
+        component.widget.move_cursor(CursorMove::Jump(2, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET));
+        assert_eq!(component.cursor(), (0, 8));
+        // unmatched brackets are left
+        component.widget.move_cursor(CursorMove::Jump(3, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MATCH_BRACKET));
+        assert_eq!(component.cursor(), (3, 0));
+    }
+
+    #[test]
+    fn should_highlight_matching_brackets() {
+        let mut component = TextArea::new(lines(&["f(x)", "(]"])).match_brackets(true);
+        assert_eq!(component.bracket_highlights(), vec![]);
+        component.perform(Cmd::Move(Direction::Right));
+        let style = Style::default().add_modifier(TextModifiers::BOLD | TextModifiers::UNDERLINED);
+        assert_eq!(
+            component.bracket_highlights(),
+            vec![((0, 1), style), ((0, 3), style)]
+        );
+        // unmatched brackets are highlighted only if their style is set
+        component.perform(Cmd::Move(Direction::Down));
+        assert_eq!(component.bracket_highlights(), vec![]);
+        let error = Style::default().fg(Color::Red);
+        let mut component = component.unmatched_bracket_style(error);
+        assert_eq!(component.bracket_highlights(), vec![((1, 1), error)]);
+        let text = component.state();
+        component.attr(
+            Attribute::Custom(TEXTAREA_MATCH_BRACKETS),
+            AttrValue::Flag(false),
+        );
+        assert_eq!(component.bracket_highlights(), vec![]);
+        assert_eq!(component.state(), text);
+    }
+
+    #[test]
+    fn should_replace_selection() {
+        let mut component = TextArea::new(lines(&["hello world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_WORD_FORWARD));
+        component.perform(Cmd::Type('y'));
+        assert_eq!(component.widget.lines(), &lines(&["yworld"]));
+        assert_eq!(component.widget.selection_range(), None);
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["hello world"]));
+        // delete the selection only
+        component.perform(Cmd::GoTo(Position::Begin));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Delete);
+        assert_eq!(component.widget.lines(), &lines(&["ello world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Cancel);
+        assert_eq!(component.widget.lines(), &lines(&["llo world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SELECT_START));
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DELETE_SELECTION));
+        assert_eq!(component.widget.lines(), &lines(&[""]));
+        // without a selection, nothing is deleted
+        component.set_lines(lines(&["foo"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DELETE_SELECTION));
+        assert_eq!(component.widget.lines(), &lines(&["foo"]));
+    }
+
+    #[test]
+    fn should_join_lines() {
+        let mut component = TextArea::new(lines(&["foo", "    bar", "", "baz"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_JOIN_LINES));
+        assert_eq!(component.widget.lines(), &lines(&["foo bar", "", "baz"]));
+        assert_eq!(component.widget.cursor(), (0, 3));
+        // no separator before a blank line
+        component.perform(Cmd::Custom(TEXTAREA_CMD_JOIN_LINES));
+        assert_eq!(component.widget.lines(), &lines(&["foo bar", "baz"]));
+        component.attr(
+            Attribute::Custom(TEXTAREA_JOIN_SEPARATOR),
+            AttrValue::String(String::new()),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_JOIN_LINES));
+        assert_eq!(component.widget.lines(), &lines(&["foo barbaz"]));
+        assert_eq!(component.widget.cursor(), (0, 7));
+        // no-op on the last line
+        component.perform(Cmd::Custom(TEXTAREA_CMD_JOIN_LINES));
+        assert_eq!(component.widget.lines(), &lines(&["foo barbaz"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.widget.lines(), &lines(&["foo bar", "baz"]));
+    }
+
+    #[test]
+    fn should_call_on_change() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let log = changes.clone();
+        let mut component = TextArea::new(lines(&[""])).on_change(Box::new(move |textarea| {
+            log.borrow_mut().push(textarea.text())
+        }));
+        component.perform(Cmd::Type('a'));
+        component.perform(Cmd::Move(Direction::Left));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REDO));
+        // deleting nothing doesn't change the text
+        component.perform(Cmd::GoTo(Position::Begin));
+        component.perform(Cmd::Delete);
+        component.insert_str("b");
+        assert_eq!(*changes.borrow(), vec!["a", "", "a", "ba"]);
+    }
+
+    #[test]
+    fn should_coalesce_typing_in_undo() {
+        let mut component = TextArea::new(lines(&[""])).undo_coalesce(true);
+        for ch in "foo bar".chars() {
+            component.perform(Cmd::Type(ch));
+        }
+        component.perform(Cmd::Move(Direction::Left));
+        component.perform(Cmd::Type('x'));
+        component.perform(Cmd::Type('y'));
+        assert_eq!(component.text(), "foo baxyr");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "foo bar");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "foo ");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "");
+        // without coalescing each char is a step
+        let mut component = TextArea::new(lines(&[""]));
+        component.perform(Cmd::Type('a'));
+        component.perform(Cmd::Type('b'));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "a");
+    }
+
+    #[test]
+    fn should_auto_close_pairs() {
+        let mut component = TextArea::new(lines(&[""])).auto_close(true);
+        component.perform(Cmd::Type('('));
+        assert_eq!(component.text(), "()");
+        assert_eq!(component.widget.cursor(), (0, 1));
+        component.perform(Cmd::Type('"'));
+        component.perform(Cmd::Type('a'));
+        assert_eq!(component.text(), "(\"a\")");
+        // type over the closers
+        component.perform(Cmd::Type('"'));
+        component.perform(Cmd::Type(')'));
+        assert_eq!(component.text(), "(\"a\")");
+        assert_eq!(component.widget.cursor(), (0, 5));
+        // delete an empty pair
+        component.perform(Cmd::Type('['));
+        component.perform(Cmd::Delete);
+        assert_eq!(component.text(), "(\"a\")");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "(\"a\")[]");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "(\"a\")");
+        // custom pairs
+        let mut component = TextArea::new(lines(&[""]))
+            .auto_close(true)
+            .auto_close_pairs("<>");
+        component.perform(Cmd::Type('<'));
+        component.perform(Cmd::Type('('));
+        assert_eq!(component.text(), "<(>");
+        // only the inserted closers are typed over
+        let mut component = TextArea::new(lines(&[")"])).auto_close(true);
+        component.perform(Cmd::Type(')'));
+        assert_eq!(component.text(), "))");
+        component.perform(Cmd::Type('('));
+        component.perform(Cmd::Type('('));
+        component.perform(Cmd::Type('x'));
+        assert_eq!(component.text(), ")((x)))");
+        component.perform(Cmd::Type(')'));
+        component.perform(Cmd::Type(')'));
+        component.perform(Cmd::Type(')'));
+        assert_eq!(component.text(), ")((x))))");
+        component.perform(Cmd::Type('('));
+        component.perform(Cmd::Move(Direction::Left));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Type(')'));
+        assert_eq!(component.text(), ")((x)))()))");
+        // only the inserted closers are deleted along with their opener
+        let mut component = TextArea::new(lines(&["()"]))
+            .auto_close(true)
+            .cursor_at(0, 1);
+        component.perform(Cmd::Delete);
+        assert_eq!(component.text(), ")");
+        let mut component = TextArea::new(lines(&[""])).auto_close(true);
+        for ch in "(([x".chars() {
+            component.perform(Cmd::Type(ch));
+        }
+        component.perform(Cmd::Delete);
+        component.perform(Cmd::Delete);
+        component.perform(Cmd::Delete);
+        assert_eq!(component.text(), "()");
+        // quotes aren't closed after a word
+        let mut component = TextArea::new(lines(&[""])).auto_close(true);
+        for ch in "don't 'x".chars() {
+            component.perform(Cmd::Type(ch));
+        }
+        assert_eq!(component.text(), "don't 'x'");
+        // disabled
+        let mut component = TextArea::new(lines(&[""]));
+        component.perform(Cmd::Type('('));
+        assert_eq!(component.text(), "(");
+    }
+
+    #[test]
+    fn should_duplicate_line() {
+        let mut component = TextArea::new(lines(&["hello", ""]));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.text(), "hello\nhello\n");
+        assert_eq!(component.widget.cursor(), (1, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_BOTTOM));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.text(), "hello\nhello\n\n");
+        assert_eq!(component.widget.cursor(), (3, 0));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "hello\n");
+    }
+
+    #[test]
+    fn should_move_line_up_and_down() {
+        let mut component = TextArea::new(lines(&["a", "bb", "c"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_UP));
+        assert_eq!(component.text(), "a\nbb\nc");
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Move(Direction::Right));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_DOWN));
+        assert_eq!(component.text(), "a\nc\nbb");
+        assert_eq!(component.widget.cursor(), (2, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_DOWN));
+        assert_eq!(component.text(), "a\nc\nbb");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_UP));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_MOVE_LINE_UP));
+        assert_eq!(component.text(), "bb\na\nc");
+        assert_eq!(component.widget.cursor(), (0, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "a\nbb\nc");
+    }
+
+    #[test]
+    fn should_reject_edits_exceeding_max_length() {
+        let mut component = TextArea::new(lines(&["abc"])).max_length(5);
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Type('d'));
+        component.perform(Cmd::Type('e'));
+        assert_eq!(component.text(), "abc\nd");
+        assert_eq!(component.widget.cursor(), (1, 1));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.text(), "abc\nd");
+        component.perform(Cmd::Delete);
+        component.perform(Cmd::Type('x'));
+        assert_eq!(component.text(), "abc\nx");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "abc\nd");
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn should_fit_pasted_text_to_max_length() {
+        let component = TextArea::new(lines(&["abc"])).max_length(5);
+        assert_eq!(component.fit_limits("d\nef"), "d\n");
+        let component = TextArea::new(lines(&["abc"]));
+        assert_eq!(component.fit_limits("d\nef"), "d\nef");
+    }
+
+    #[test]
+    fn should_reject_edits_exceeding_max_lines() {
+        let mut component = TextArea::new(lines(&["a"])).max_lines(2);
+        component.perform(Cmd::GoTo(Position::End));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Type('b'));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_NEWLINE));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_DUPLICATE_LINE));
+        assert_eq!(component.text(), "a\nb");
+        assert_eq!(component.widget.cursor(), (1, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn should_fit_pasted_text_to_max_lines() {
+        let component = TextArea::new(lines(&["a"])).max_lines(2);
+        assert_eq!(component.fit_limits("b\nc\n"), "b\nc");
+    }
+
+    #[test]
+    fn should_set_cursor() {
+        let mut component = TextArea::new(lines(&["hello", "world"]));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SET_CURSOR));
+        assert_eq!(component.cursor(), (0, 0));
+        component.attr(
+            Attribute::Custom(TEXTAREA_CURSOR_POSITION),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Usize(1),
+                PropValue::Usize(2),
+            ))),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SET_CURSOR));
+        assert_eq!(component.cursor(), (1, 2));
+        component.attr(
+            Attribute::Custom(TEXTAREA_CURSOR_POSITION),
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Usize(9),
+                PropValue::Usize(9),
+            ))),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SET_CURSOR));
+        assert_eq!(component.cursor(), (1, 5));
+    }
+
+    #[test]
+    fn should_goto_line() {
+        let mut component = TextArea::new(lines(&["a", "b", "c"]));
+        for (line, row) in [(2, 1), (0, 0), (10, 2)] {
+            component.attr(
+                Attribute::Custom(TEXTAREA_GOTO_LINE),
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(line))),
+            );
+            component.perform(Cmd::Custom(TEXTAREA_CMD_GOTO_LINE));
+            assert_eq!(component.cursor(), (row, 0));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_replace_search_matches() {
+        let mut component = TextArea::new(lines(&["foo bar", "bar foo foo"]));
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("f(o+)")),
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_REPLACE_WITH),
+            AttrValue::String(String::from("b${1}m")),
+        );
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REPLACE_NEXT));
+        assert_eq!(component.text(), "boom bar\nbar foo foo");
+        assert_eq!(component.cursor(), (1, 4));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_REPLACE_ALL));
+        assert_eq!(component.text(), "boom bar\nbar boom boom");
+        component.perform(Cmd::Custom(TEXTAREA_CMD_UNDO));
+        assert_eq!(component.text(), "boom bar\nbar foo foo");
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_toggle_search_case_and_regex() {
+        let mut component = TextArea::new(lines(&["a.c", "ABC"]));
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("a.c")),
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_CASE_INSENSITIVE),
+            AttrValue::Flag(true),
+        );
+        assert_eq!(
+            component.widget.search_pattern().unwrap().as_str(),
+            "(?i)a.c"
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_REGEX),
+            AttrValue::Flag(false),
+        );
+        assert_eq!(
+            component.widget.search_pattern().unwrap().as_str(),
+            r"(?i)a\.c"
+        );
+        assert_eq!(
+            component.query(Attribute::Custom(TEXTAREA_SEARCH_OPTIONS)),
+            Some(AttrValue::Payload(PropPayload::Tup4((
+                PropValue::Bool(false),
+                PropValue::Bool(true),
+                PropValue::Bool(false),
+                PropValue::Bool(true),
+            ))))
+        );
+        // invalid patterns leave the previous one active
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_REGEX),
+            AttrValue::Flag(true),
+        );
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("a(")),
+        );
+        assert_eq!(
+            component.widget.search_pattern().unwrap().as_str(),
+            "(?i)a.c"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "search")]
+    fn should_get_search_matches() {
+        let mut component = TextArea::new(lines(&["foo", "bar foo"]));
+        assert_eq!(component.search_matches(), (0, 0));
+        component.attr(
+            Attribute::Custom(TEXTAREA_SEARCH_PATTERN),
+            AttrValue::String(String::from("foo")),
+        );
+        assert_eq!(component.search_matches(), (1, 2));
+        component.perform(Cmd::Move(Direction::Down));
+        component.perform(Cmd::Custom(TEXTAREA_CMD_SEARCH_FORWARD));
+        assert_eq!(component.search_matches(), (2, 2));
+    }
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|x| x.to_string()).collect()
+    }
+
+    fn draw(component: &mut TextArea, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| component.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
 }
@@ -0,0 +1,41 @@
+//! # line_ending
+//!
+//! Module which provides the line endings used to join the lines of the text
+
+/// The sequence which ends the lines of the text, when it is exported as a single string
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, used by Unix-like systems
+    #[default]
+    Lf,
+    /// `\r\n`, used by Windows
+    CrLf,
+    /// `\r`, used by classic Mac OS
+    Cr,
+}
+
+impl LineEnding {
+    /// Get the sequence of the line ending
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_get_line_ending_sequence() {
+        assert_eq!(LineEnding::default().as_str(), "\n");
+        assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+        assert_eq!(LineEnding::Cr.as_str(), "\r");
+    }
+}
@@ -0,0 +1,1351 @@
+//! # render
+//!
+//! Module which provides the renderer of the textarea, which draws the gutter and the visible lines of the text.
+//!
+//! It replaces the renderer of `tui-textarea`, which draws all the line numbers with one style and keeps
+//! its scroll position private: the row of the cursor can't be told from outside the widget, so its
+//! line number can't be restyled over the widget's output. The scroll position is kept by [`Viewport`]
+//! instead, while the widget only holds the text, the cursor and the styles
+
+use super::wrap::{break_indent, char_width, wrap_line};
+use super::TextAreaWidget;
+
+use std::collections::HashMap;
+use std::ops::Range;
+use tuirealm::props::{Color, Style, TextModifiers};
+use tuirealm::ratatui::buffer::Buffer;
+use tuirealm::ratatui::layout::Rect;
+use tuirealm::ratatui::widgets::{StatefulWidget, Widget};
+use unicode_width::UnicodeWidthChar;
+
+/// Style of the selected text, which is the default one of the widget, never changed by the component
+const SELECTION_STYLE: Style = Style::new().bg(Color::LightBlue);
+
+/// A callback which styles the line at the given row, returning the styles of its char ranges.
+/// The ranges are char indices, not byte offsets
+pub type LineStyler = Box<dyn Fn(usize, &str) -> Vec<(Range<usize>, Style)>>;
+
+/// Position of the text shown at the top-left corner of the textarea
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// First visible row
+    pub row: usize,
+    /// First visible display column
+    pub col: usize,
+    /// Wrapped rows of the first visible line hidden above the area, when that line is taller than
+    /// the area
+    pub skip: usize,
+}
+
+impl Viewport {
+    /// Scroll the viewport to show the line at `row` at the top, starting from its first row
+    pub fn set_row(&mut self, row: usize) {
+        self.row = row;
+        self.skip = 0;
+    }
+
+    /// Scroll the viewport the least needed to keep `cursor`, given as row and display column,
+    /// visible in an area of `width` x `height` with the given rows and columns of context around it.
+    /// `rows` is the amount of lines, which limits the context below the cursor
+    fn follow(
+        &mut self,
+        (row, col): (usize, usize),
+        width: usize,
+        height: usize,
+        rows: usize,
+        (rows_off, cols_off): (usize, usize),
+    ) {
+        let below = rows_off.min(rows.saturating_sub(row + 1));
+        self.row = next_top(self.row, row, height, (rows_off, below));
+        self.col = next_top(self.col, col, width, (cols_off, cols_off));
+        self.skip = 0;
+    }
+}
+
+/// Get the first visible position to keep `cursor` in a view of `len` cells starting at `top`,
+/// with `before` and `after` cells of context around it, up to half of the view
+fn next_top(top: usize, cursor: usize, len: usize, (before, after): (usize, usize)) -> usize {
+    let len = len.max(1);
+    let before = before.min((len - 1) / 2);
+    let after = after.min((len - 1) / 2);
+    if cursor < top + before {
+        cursor.saturating_sub(before)
+    } else if top + len <= cursor + after {
+        cursor + after + 1 - len
+    } else {
+        top
+    }
+}
+
+/// A row of the area, showing the chars from `start` to `end` of the line at `row`,
+/// after `indent` blank columns
+struct VisualRow {
+    row: usize,
+    start: usize,
+    end: usize,
+    indent: usize,
+}
+
+/// A cell of a rendered line
+struct Cell {
+    symbol: String,
+    /// Char index in the line
+    index: usize,
+    col: usize,
+    width: usize,
+    style: Style,
+}
+
+/// Renders the textarea widget in place of its own renderer, in order to style the gutter
+pub struct Renderer<'a> {
+    widget: &'a TextAreaWidget<'a>,
+    cursor_gutter_style: Style,
+    line_wrap: bool,
+    wrap_indent: bool,
+    whitespace: Option<(char, char)>,
+    tab_char: Option<char>,
+    rulers: (Vec<usize>, Style),
+    indent_guide_style: Option<Style>,
+    follow_cursor: bool,
+    relative_line_numbers: bool,
+    line_number_width: usize,
+    highlights: Vec<((usize, usize), Style)>,
+    line_highlights: Option<&'a HashMap<usize, Style>>,
+    scroll_off: usize,
+    center_cursor: bool,
+    line_styler: Option<&'a LineStyler>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Instantiates a new `Renderer` for `widget`
+    pub fn new(widget: &'a TextAreaWidget<'a>) -> Self {
+        Self {
+            widget,
+            cursor_gutter_style: Style::default(),
+            line_wrap: false,
+            wrap_indent: false,
+            whitespace: None,
+            tab_char: None,
+            rulers: (Vec::new(), Style::default()),
+            indent_guide_style: None,
+            follow_cursor: true,
+            relative_line_numbers: false,
+            line_number_width: 0,
+            highlights: Vec::new(),
+            line_highlights: None,
+            scroll_off: 0,
+            center_cursor: false,
+            line_styler: None,
+        }
+    }
+
+    /// Set the style patched on the line number of the cursor row
+    pub fn cursor_gutter_style(mut self, style: Style) -> Self {
+        self.cursor_gutter_style = style;
+        self
+    }
+
+    /// Set whether the lines longer than the area are wrapped on the next visual rows
+    pub fn line_wrap(mut self, enabled: bool) -> Self {
+        self.line_wrap = enabled;
+        self
+    }
+
+    /// Set whether the rows continuing a wrapped line are indented as the line
+    pub fn wrap_indent(mut self, enabled: bool) -> Self {
+        self.wrap_indent = enabled;
+        self
+    }
+
+    /// Set the glyphs drawn in place of the leading and trailing spaces and of tabs.
+    /// Whitespace is drawn as is if `None`
+    pub fn whitespace(mut self, glyphs: Option<(char, char)>) -> Self {
+        self.whitespace = glyphs;
+        self
+    }
+
+    /// Set the glyph drawn on the first column of the tabs, even if whitespace is not shown.
+    /// Tabs are drawn as is if `None`
+    pub fn tab_char(mut self, glyph: Option<char>) -> Self {
+        self.tab_char = glyph;
+        self
+    }
+
+    /// Set the display columns, starting from 0, where vertical rulers are drawn with `style`
+    /// across the text area
+    pub fn rulers(mut self, cols: Vec<usize>, style: Style) -> Self {
+        self.rulers = (cols, style);
+        self
+    }
+
+    /// Set the style of the indent guides, drawn at the tab stops in the indentation of the lines.
+    /// Guides are not drawn if `None`
+    pub fn indent_guides(mut self, style: Option<Style>) -> Self {
+        self.indent_guide_style = style;
+        self
+    }
+
+    /// Set whether the line numbers are displayed as the distance from the cursor row,
+    /// which keeps its own number
+    pub fn relative_line_numbers(mut self, enabled: bool) -> Self {
+        self.relative_line_numbers = enabled;
+        self
+    }
+
+    /// Set the minimum amount of columns of the line numbers, so that the gutter doesn't grow
+    /// along with the amount of lines
+    pub fn line_number_width(mut self, width: usize) -> Self {
+        self.line_number_width = width;
+        self
+    }
+
+    /// Set the styles patched on the chars at the given positions, such as the matching brackets
+    pub fn highlights(mut self, highlights: Vec<((usize, usize), Style)>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Set the styles of the whole rows of the lines, by line index, under the styles of the text
+    pub fn line_highlights(mut self, highlights: Option<&'a HashMap<usize, Style>>) -> Self {
+        self.line_highlights = highlights;
+        self
+    }
+
+    /// Set the amount of rows and columns kept visible around the cursor when scrolling
+    pub fn scroll_off(mut self, scroll_off: usize) -> Self {
+        self.scroll_off = scroll_off;
+        self
+    }
+
+    /// Set whether the viewport is scrolled to keep the cursor row in the middle of the area,
+    /// except near the start and the end of the text
+    pub fn center_cursor(mut self, enabled: bool) -> Self {
+        self.center_cursor = enabled;
+        self
+    }
+
+    /// Set the callback which styles the lines, such as to highlight syntax
+    pub fn line_styler(mut self, styler: Option<&'a LineStyler>) -> Self {
+        self.line_styler = styler;
+        self
+    }
+
+    /// Set whether the viewport is scrolled to keep the cursor visible
+    pub fn follow_cursor(mut self, enabled: bool) -> Self {
+        self.follow_cursor = enabled;
+        self
+    }
+
+    /// Get the text position, as row and column, at the cell `(x, y)` of `area`, where the textarea has been
+    /// rendered with `viewport`.
+    /// Returns `None` if the cell is outside of the text, such as on the borders or on the gutter
+    pub fn position_at(
+        &self,
+        area: Rect,
+        viewport: &Viewport,
+        (x, y): (u16, u16),
+    ) -> Option<(usize, usize)> {
+        let area = self.text_area(area);
+        let gutter = (self.gutter_width() as u16).min(area.width);
+        let text_width = area.width - gutter;
+        if x < area.x + gutter || x >= area.right() || y < area.y || y >= area.bottom() {
+            return None;
+        }
+        let visual = self
+            .layout(viewport, text_width as usize, area.height as usize)
+            .into_iter()
+            .nth((y - area.y) as usize)?;
+        let target = self.left_col(viewport, &visual)
+            + ((x - area.x - gutter) as usize).saturating_sub(visual.indent);
+        // find the char covering the target column, or the end of the visual row
+        let tab_len = self.widget.tab_length();
+        let mut col = 0;
+        let line = &self.widget.lines()[visual.row];
+        let len = line.chars().count();
+        let index = line
+            .chars()
+            .position(|ch| {
+                col += self.char_width(ch, col, tab_len);
+                col > target
+            })
+            .unwrap_or(len);
+        let last = match visual.end == len {
+            true => len,
+            false => visual.end.saturating_sub(1),
+        };
+        Some((
+            visual.row,
+            index.clamp(visual.start, last.max(visual.start)),
+        ))
+    }
+
+    /// Get the area inside the block of the widget
+    pub fn text_area(&self, area: Rect) -> Rect {
+        match self.widget.block() {
+            Some(block) => block.inner(area),
+            None => area,
+        }
+    }
+
+    /// Get the amount of rows needed to display all the lines in `width` columns, including the gutter
+    pub fn text_rows(&self, width: u16) -> usize {
+        let text_width = (width as usize).saturating_sub(self.gutter_width());
+        (0..self.widget.lines().len())
+            .map(|row| self.visual_rows(row, text_width).len())
+            .sum()
+    }
+
+    /// Get the width of the gutter, which is empty if line numbers are not displayed
+    fn gutter_width(&self) -> usize {
+        match self.widget.line_number_style() {
+            Some(_) => {
+                let digits = self.widget.lines().len().to_string().len();
+                digits.max(self.line_number_width) + 2
+            }
+            None => 0,
+        }
+    }
+
+    /// Get the display column of `col` in `row`
+    fn display_col(&self, row: usize, col: usize) -> usize {
+        let tab_len = self.widget.tab_length();
+        self.widget.lines()[row]
+            .chars()
+            .take(col)
+            .fold(0, |x, ch| x + self.char_width(ch, x, tab_len))
+    }
+
+    fn char_width(&self, ch: char, col: usize, tab_len: u8) -> usize {
+        match self.widget.mask_char() {
+            Some(mask) => mask.width().unwrap_or(0),
+            None => char_width(ch, col, tab_len),
+        }
+    }
+
+    /// Get the char ranges of `line` matching the search pattern
+    #[cfg(feature = "search")]
+    fn search_matches(&self, line: &str) -> Vec<(usize, usize)> {
+        let Some(pattern) = self.widget.search_pattern() else {
+            return Vec::new();
+        };
+        pattern
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .map(|m| {
+                let start = line[..m.start()].chars().count();
+                (start, start + m.as_str().chars().count())
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "search")]
+    fn search_style(&self) -> Style {
+        self.widget.search_style()
+    }
+
+    #[cfg(not(feature = "search"))]
+    fn search_matches(&self, _: &str) -> Vec<(usize, usize)> {
+        Vec::new()
+    }
+
+    #[cfg(not(feature = "search"))]
+    fn search_style(&self) -> Style {
+        Style::default()
+    }
+
+    /// Get the char ranges of the visual rows of `row`, which are wrapped at `width` columns
+    /// if line wrap is enabled
+    fn visual_rows(&self, row: usize, width: usize) -> Vec<(usize, usize)> {
+        let line = &self.widget.lines()[row];
+        let len = line.chars().count();
+        if !self.line_wrap {
+            return vec![(0, len)];
+        }
+        let indent = self.break_indent(row, width);
+        let mut starts = match self.widget.mask_char() {
+            Some(mask) => wrap_line(
+                &line.chars().map(|_| mask).collect::<String>(),
+                width,
+                indent,
+                self.widget.tab_length(),
+            ),
+            None => wrap_line(line, width, indent, self.widget.tab_length()),
+        };
+        // the cursor past the end of a full row is drawn on a row of its own
+        let last = starts.last().copied().unwrap_or_default();
+        let last_width = match last {
+            0 => width,
+            _ => width.saturating_sub(indent),
+        };
+        let (cursor_row, cursor_col) = self.widget.cursor();
+        if row == cursor_row
+            && cursor_col >= len
+            && self.display_col(row, len) - self.display_col(row, last) >= last_width.max(1)
+        {
+            starts.push(len);
+        }
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, start)| (*start, starts.get(i + 1).copied().unwrap_or(len)))
+            .collect()
+    }
+
+    /// Get the indent of the rows continuing the line at `row`, wrapped at `width` columns
+    fn break_indent(&self, row: usize, width: usize) -> usize {
+        match self.line_wrap && self.wrap_indent && self.widget.mask_char().is_none() {
+            true => break_indent(&self.widget.lines()[row], width, self.widget.tab_length()),
+            false => 0,
+        }
+    }
+
+    /// Get the visual rows shown in an area of `width` x `height`, starting from `viewport`
+    fn layout(&self, viewport: &Viewport, width: usize, height: usize) -> Vec<VisualRow> {
+        (viewport.row..self.widget.lines().len())
+            .flat_map(|row| {
+                let indent = self.break_indent(row, width);
+                let skip = if row == viewport.row {
+                    viewport.skip
+                } else {
+                    0
+                };
+                self.visual_rows(row, width)
+                    .into_iter()
+                    .skip(skip)
+                    .map(move |(start, end)| VisualRow {
+                        row,
+                        start,
+                        end,
+                        indent: if start == 0 { 0 } else { indent },
+                    })
+            })
+            .take(height)
+            .collect()
+    }
+
+    /// Get the display column of the line shown at the left of `visual`
+    fn left_col(&self, viewport: &Viewport, visual: &VisualRow) -> usize {
+        match self.line_wrap {
+            true => self.display_col(visual.row, visual.start),
+            false => viewport.col,
+        }
+    }
+
+    /// Get the amount of rows kept visible around the cursor, which is as many as possible
+    /// to keep the cursor centered
+    fn rows_scroll_off(&self) -> usize {
+        match self.center_cursor {
+            true => usize::MAX,
+            false => self.scroll_off,
+        }
+    }
+
+    /// Scroll the viewport the least needed to keep the cursor visible, when lines are wrapped
+    /// in an area of `width` x `height`, with up to `scroll_off` rows of context around it.
+    /// A cursor line taller than the area is shown from the row which keeps the cursor visible
+    fn follow_wrapped(&self, viewport: &mut Viewport, width: usize, height: usize) {
+        let (row, col) = self.widget.cursor();
+        let line_rows = self.visual_rows(row, width).len();
+        let cursor_rows = self
+            .visual_rows(row, width)
+            .iter()
+            .rposition(|(start, _)| *start <= col)
+            .unwrap_or_default()
+            + 1;
+        let scroll_off = self.rows_scroll_off().min(height.saturating_sub(1) / 2);
+        // count the rows of context below the cursor
+        let mut below = line_rows - cursor_rows;
+        let mut next = row + 1;
+        while below < scroll_off && next < self.widget.lines().len() {
+            below += self.visual_rows(next, width).len();
+            next += 1;
+        }
+        // get the first row which keeps the cursor visible, going up until the area is filled
+        let mut top = row;
+        let mut rows = cursor_rows + below.min(scroll_off);
+        while top > 0 {
+            let above = self.visual_rows(top - 1, width).len();
+            if rows + above > height {
+                break;
+            }
+            rows += above;
+            top -= 1;
+        }
+        // get the last row which keeps the context above the cursor visible
+        let mut bottom = row;
+        let mut above = cursor_rows - 1;
+        while above < scroll_off && bottom > 0 {
+            bottom -= 1;
+            above += self.visual_rows(bottom, width).len();
+        }
+        let (prev_row, prev_skip) = (viewport.row, viewport.skip);
+        viewport.row = viewport.row.min(bottom).max(top);
+        viewport.col = 0;
+        viewport.skip = match viewport.row == row {
+            true => {
+                let skip = if prev_row == row { prev_skip } else { 0 };
+                let min = (cursor_rows + below.min(scroll_off)).saturating_sub(height);
+                let max = (cursor_rows - 1).saturating_sub(scroll_off);
+                skip.min(max).max(min)
+            }
+            false => 0,
+        };
+    }
+
+    /// Get the style patched on the whitespace glyphs, which is the line number style if set
+    fn whitespace_style(&self) -> Style {
+        self.widget
+            .line_number_style()
+            .unwrap_or_else(|| Style::default().add_modifier(TextModifiers::DIM))
+    }
+
+    /// Make the cells of the line at `row`
+    fn line_cells(&self, row: usize) -> Vec<Cell> {
+        let line = &self.widget.lines()[row];
+        let tab_len = self.widget.tab_length();
+        let cursor = self.widget.cursor();
+        let selection = self.widget.selection_range();
+        let matches = self.search_matches(line);
+        let spans = self
+            .line_styler
+            .map(|styler| styler(row, line))
+            .unwrap_or_default();
+        let len = line.chars().count();
+        // only the leading and trailing spaces are made visible
+        let leading = line.chars().take_while(|ch| *ch == ' ').count();
+        let trailing = len - line.chars().rev().take_while(|ch| *ch == ' ').count();
+        let indent = line
+            .chars()
+            .take_while(|ch| *ch == ' ' || *ch == '\t')
+            .count();
+        // the tab char is drawn even if the other whitespace is not
+        let tab = self.tab_char.or(self.whitespace.map(|(_, tab)| tab));
+        let mut cells: Vec<Cell> = Vec::with_capacity(line.len() + 1);
+        let mut col = 0;
+        for (i, ch) in line.chars().enumerate() {
+            let width = self.char_width(ch, col, tab_len);
+            let indent_guide = self
+                .indent_guide_style
+                .filter(|_| i < indent && tab_len > 0 && col % tab_len as usize == 0);
+            let pad = " ".repeat(width.saturating_sub(1));
+            let glyph = match (self.widget.mask_char(), indent_guide, ch) {
+                (None, Some(style), _) => Some((format!("│{pad}"), style)),
+                (None, None, '\t') => {
+                    tab.map(|tab| (format!("{tab}{pad}"), self.whitespace_style()))
+                }
+                (None, None, ' ') if i < leading || i >= trailing => self
+                    .whitespace
+                    .map(|(space, _)| (space.to_string(), self.whitespace_style())),
+                _ => None,
+            };
+            let symbol = match (self.widget.mask_char(), ch) {
+                (Some(mask), _) => mask.to_string(),
+                (None, '\t') => " ".repeat(width),
+                (None, ch) => ch.to_string(),
+            };
+            // the styles of the text are patched over the style given by the line styler
+            let base = spans
+                .iter()
+                .filter(|(range, _)| range.contains(&i))
+                .fold(Style::default(), |style, (_, span)| style.patch(*span));
+            let style = if (row, i) == cursor {
+                base.patch(self.widget.cursor_style())
+            } else if matches
+                .iter()
+                .any(|(start, end)| (*start..*end).contains(&i))
+            {
+                base.patch(self.search_style())
+            } else if selection.is_some_and(|(start, end)| (start..end).contains(&(row, i))) {
+                base.patch(SELECTION_STYLE)
+            } else if row == cursor.0 {
+                base.patch(self.widget.cursor_line_style())
+            } else {
+                base
+            };
+            let style = self
+                .highlights
+                .iter()
+                .filter(|(pos, _)| *pos == (row, i))
+                .fold(style, |style, (_, highlight)| style.patch(*highlight));
+            let (symbol, style) = match glyph {
+                Some((glyph, _)) if (row, i) == cursor => (glyph, style),
+                Some((glyph, glyph_style)) => (glyph, style.patch(glyph_style)),
+                None => (symbol, style),
+            };
+            match cells.last_mut() {
+                // zero-width chars are combined with the previous one
+                Some(last) if width == 0 && ch != '\t' => last.symbol.push(ch),
+                _ => cells.push(Cell {
+                    symbol,
+                    index: i,
+                    col,
+                    width,
+                    style,
+                }),
+            }
+            col += width;
+        }
+        // the cursor and a selection going on the next line are drawn past the end of the line
+        let select_at_end =
+            selection.is_some_and(|((start, _), (end, _))| (start..end).contains(&row));
+        if row == cursor.0 && cursor.1 >= len {
+            cells.push(Cell {
+                symbol: String::from(" "),
+                index: len,
+                col,
+                width: 1,
+                style: self.widget.cursor_style(),
+            });
+        } else if select_at_end {
+            cells.push(Cell {
+                symbol: String::from(" "),
+                index: len,
+                col,
+                width: 1,
+                style: SELECTION_STYLE,
+            });
+        }
+        cells
+    }
+
+    /// Render the gutter of `row` in `area`.
+    /// The line number is only drawn on the first visual row of the line
+    fn render_gutter(&self, row: usize, first: bool, area: Rect, buf: &mut Buffer) {
+        if let Some(style) = self.widget.line_number_style() {
+            let cursor_row = self.widget.cursor().0;
+            let style = match row == cursor_row {
+                true => style.patch(self.cursor_gutter_style),
+                false => style,
+            };
+            let width = self.gutter_width();
+            let number = match (first, self.relative_line_numbers && row != cursor_row) {
+                (false, _) => " ".repeat(width),
+                (true, true) => format!(" {:>1$} ", row.abs_diff(cursor_row), width - 2),
+                (true, false) => format!(" {:>1$} ", row + 1, width - 2),
+            };
+            buf.set_stringn(area.x, area.y, number, area.width as usize, style);
+        }
+    }
+
+    /// Render `cells` in `area`, skipping the first `left` columns
+    fn render_cells<'c>(
+        &self,
+        cells: impl Iterator<Item = &'c Cell>,
+        left: usize,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let right = left + area.width as usize;
+        for cell in cells {
+            if cell.col >= left && cell.col + cell.width <= right {
+                buf.set_stringn(
+                    area.x + (cell.col - left) as u16,
+                    area.y,
+                    &cell.symbol,
+                    cell.width,
+                    cell.style,
+                );
+            }
+        }
+    }
+
+    /// Render the rulers in `area`, scrolled by `left` columns. Blank cells are drawn as a line,
+    /// while the style of the text is patched
+    fn render_rulers(&self, left: usize, area: Rect, buf: &mut Buffer) {
+        let (cols, style) = &self.rulers;
+        for col in cols
+            .iter()
+            .filter(|col| (left..left + area.width as usize).contains(col))
+        {
+            let x = area.x + (col - left) as u16;
+            for y in area.top()..area.bottom() {
+                let cell = &mut buf[(x, y)];
+                if cell.symbol() == " " {
+                    cell.set_symbol("│");
+                }
+                cell.set_style(*style);
+            }
+        }
+    }
+
+    /// Render the placeholder text, shown in place of the empty text
+    fn render_placeholder(&self, area: Rect, buf: &mut Buffer) {
+        buf.set_stringn(area.x, area.y, " ", 1, self.widget.cursor_style());
+        buf.set_stringn(
+            area.x + 1,
+            area.y,
+            self.widget.placeholder_text(),
+            area.width.saturating_sub(1) as usize,
+            Style::default(),
+        );
+    }
+}
+
+impl StatefulWidget for Renderer<'_> {
+    type State = Viewport;
+
+    fn render(self, area: Rect, buf: &mut Buffer, viewport: &mut Viewport) {
+        if let Some(block) = self.widget.block() {
+            block.clone().render(area, buf);
+        }
+        let area = self.text_area(area);
+        if area.is_empty() {
+            return;
+        }
+        if let Some(style) = self
+            .widget
+            .placeholder_style()
+            .filter(|_| self.widget.is_empty())
+        {
+            buf.set_style(area, style);
+            self.render_placeholder(area, buf);
+            return;
+        }
+        buf.set_style(area, self.widget.style());
+        let gutter = (self.gutter_width() as u16).min(area.width);
+        let text_width = area.width - gutter;
+        let (row, col) = self.widget.cursor();
+        match (self.follow_cursor, self.line_wrap) {
+            (false, _) => {}
+            (true, true) => {
+                self.follow_wrapped(viewport, text_width as usize, area.height as usize)
+            }
+            (true, false) => viewport.follow(
+                (row, self.display_col(row, col)),
+                text_width as usize,
+                area.height as usize,
+                self.widget.lines().len(),
+                (self.rows_scroll_off(), self.scroll_off),
+            ),
+        }
+        let rows = self.layout(viewport, text_width as usize, area.height as usize);
+        let mut cells = (usize::MAX, Vec::new());
+        for (y, visual) in rows.iter().enumerate() {
+            let y = area.y + y as u16;
+            if cells.0 != visual.row {
+                cells = (visual.row, self.line_cells(visual.row));
+            }
+            let len = self.widget.lines()[visual.row].chars().count();
+            // the cells past the end of the line belong to the last visual row
+            let visible = cells.1.iter().filter(|cell| {
+                cell.index >= visual.start && (cell.index < visual.end || visual.end == len)
+            });
+            if let Some(style) = self.line_highlights.and_then(|x| x.get(&visual.row)) {
+                buf.set_style(Rect::new(area.x + gutter, y, text_width, 1), *style);
+            }
+            self.render_gutter(
+                visual.row,
+                visual.start == 0,
+                Rect::new(area.x, y, gutter, 1),
+                buf,
+            );
+            self.render_cells(
+                visible,
+                self.left_col(viewport, visual),
+                Rect::new(
+                    area.x + gutter + visual.indent as u16,
+                    y,
+                    text_width - visual.indent as u16,
+                    1,
+                ),
+                buf,
+            );
+        }
+        self.render_rulers(
+            viewport.col,
+            Rect::new(area.x + gutter, area.y, text_width, area.height),
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use tui_textarea::CursorMove;
+    use tuirealm::props::{Color, TextModifiers};
+
+    #[test]
+    fn should_render_lines_with_gutter() {
+        let mut widget = widget(&["hello", "\tworld"]);
+        widget.set_line_number_style(Style::default());
+        widget.set_tab_length(4);
+        let buf = render(&widget, 12, 3, &mut Viewport::default());
+        assert_eq!(
+            text(&buf),
+            vec![" 1 hello    ", " 2     world", "            "]
+        );
+    }
+
+    #[test]
+    fn should_style_cursor_gutter() {
+        let mut widget = widget(&["hello", "world"]);
+        let lnum_style = Style::default().fg(Color::DarkGray);
+        widget.set_line_number_style(lnum_style);
+        widget.move_cursor(CursorMove::Down);
+        let renderer = Renderer::new(&widget)
+            .cursor_gutter_style(Style::default().add_modifier(TextModifiers::BOLD));
+        let area = Rect::new(0, 0, 8, 2);
+        let mut buf = Buffer::empty(area);
+        renderer.render(area, &mut buf, &mut Viewport::default());
+        assert_eq!(buf[(1, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(1, 0)].modifier, TextModifiers::empty());
+        assert_eq!(buf[(1, 1)].fg, Color::DarkGray);
+        assert_eq!(buf[(1, 1)].modifier, TextModifiers::BOLD);
+    }
+
+    #[test]
+    fn should_render_relative_line_numbers() {
+        let mut widget = widget(&["a", "b", "c", "d"]);
+        widget.set_line_number_style(Style::default());
+        widget.move_cursor(CursorMove::Jump(2, 0));
+        let area = Rect::new(0, 0, 4, 4);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget).relative_line_numbers(true).render(
+            area,
+            &mut buf,
+            &mut Viewport::default(),
+        );
+        assert_eq!(text(&buf), vec![" 2 a", " 1 b", " 3 c", " 1 d"]);
+    }
+
+    #[test]
+    fn should_render_line_numbers_with_fixed_width() {
+        let mut widget = widget(&["a", "b"]);
+        widget.set_line_number_style(Style::default());
+        let area = Rect::new(0, 0, 6, 2);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget).line_number_width(3).render(
+            area,
+            &mut buf,
+            &mut Viewport::default(),
+        );
+        assert_eq!(text(&buf), vec!["   1 a", "   2 b"]);
+        // numbers wider than the width expand the gutter
+        let mut widget = self::widget(&(0..10).map(|_| "x").collect::<Vec<_>>());
+        widget.set_line_number_style(Style::default());
+        let area = Rect::new(0, 0, 5, 1);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget).line_number_width(1).render(
+            area,
+            &mut buf,
+            &mut Viewport::default(),
+        );
+        assert_eq!(text(&buf), vec!["  1 x"]);
+    }
+
+    #[test]
+    fn should_scroll_viewport_to_cursor() {
+        let mut widget = widget(&["0", "1", "2", "3", "4 abcdefgh"]);
+        widget.move_cursor(CursorMove::Bottom);
+        widget.move_cursor(CursorMove::End);
+        let mut viewport = Viewport::default();
+        let buf = render(&widget, 4, 2, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 3,
+                col: 7,
+                skip: 0
+            }
+        );
+        assert_eq!(text(&buf), vec!["    ", "fgh "]);
+        widget.move_cursor(CursorMove::Top);
+        render(&widget, 4, 2, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 0,
+                col: 1,
+                skip: 0
+            }
+        );
+    }
+
+    #[test]
+    fn should_keep_scroll_off_around_cursor() {
+        let mut widget = widget(&["0", "1", "2", "3", "4", "5 abcdefgh"]);
+        let area = Rect::new(0, 0, 6, 4);
+        let mut viewport = Viewport::default();
+        let render = |widget: &TextAreaWidget, viewport: &mut Viewport| {
+            let mut buf = Buffer::empty(area);
+            Renderer::new(widget)
+                .scroll_off(1)
+                .render(area, &mut buf, viewport);
+        };
+        widget.move_cursor(CursorMove::Jump(3, 0));
+        render(&widget, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 1,
+                col: 0,
+                skip: 0
+            }
+        );
+        // the context below the last line is not scrolled in
+        widget.move_cursor(CursorMove::Jump(5, 0));
+        render(&widget, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 2,
+                col: 0,
+                skip: 0
+            }
+        );
+        widget.move_cursor(CursorMove::End);
+        render(&widget, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 2,
+                col: 6,
+                skip: 0
+            }
+        );
+        widget.move_cursor(CursorMove::Jump(2, 0));
+        render(&widget, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 1,
+                col: 0,
+                skip: 0
+            }
+        );
+    }
+
+    #[test]
+    fn should_center_cursor() {
+        let mut widget = widget(&["0", "1", "2", "3", "4", "5", "6 abcdefgh"]);
+        let area = Rect::new(0, 0, 6, 3);
+        let mut viewport = Viewport::default();
+        let render = |widget: &TextAreaWidget, viewport: &mut Viewport, wrap: bool| {
+            let mut buf = Buffer::empty(area);
+            Renderer::new(widget)
+                .center_cursor(true)
+                .line_wrap(wrap)
+                .render(area, &mut buf, viewport);
+        };
+        widget.move_cursor(CursorMove::Jump(1, 0));
+        render(&widget, &mut viewport, false);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 0,
+                col: 0,
+                skip: 0
+            }
+        );
+        widget.move_cursor(CursorMove::Jump(3, 0));
+        render(&widget, &mut viewport, false);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 2,
+                col: 0,
+                skip: 0
+            }
+        );
+        widget.move_cursor(CursorMove::Jump(2, 0));
+        render(&widget, &mut viewport, true);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 1,
+                col: 0,
+                skip: 0
+            }
+        );
+        // the viewport is clamped at the end of the text, and columns are not centered
+        widget.move_cursor(CursorMove::Jump(6, 4));
+        render(&widget, &mut viewport, false);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 4,
+                col: 0,
+                skip: 0
+            }
+        );
+    }
+
+    #[test]
+    fn should_keep_scroll_off_around_cursor_when_wrapped() {
+        let mut widget = widget(&["0", "1", "2", "3", "4", "5"]);
+        let area = Rect::new(0, 0, 6, 4);
+        let mut viewport = Viewport::default();
+        let render = |widget: &TextAreaWidget, viewport: &mut Viewport| {
+            let mut buf = Buffer::empty(area);
+            Renderer::new(widget)
+                .line_wrap(true)
+                .scroll_off(1)
+                .render(area, &mut buf, viewport);
+        };
+        widget.move_cursor(CursorMove::Jump(3, 0));
+        render(&widget, &mut viewport);
+        assert_eq!(viewport.row, 1);
+        widget.move_cursor(CursorMove::Jump(1, 0));
+        render(&widget, &mut viewport);
+        assert_eq!(viewport.row, 0);
+    }
+
+    #[test]
+    fn should_not_follow_cursor() {
+        let widget = widget(&["0", "1", "2", "3"]);
+        let mut viewport = Viewport {
+            row: 2,
+            col: 0,
+            skip: 0,
+        };
+        let area = Rect::new(0, 0, 4, 2);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget)
+            .follow_cursor(false)
+            .render(area, &mut buf, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 2,
+                col: 0,
+                skip: 0
+            }
+        );
+        assert_eq!(text(&buf), vec!["2   ", "3   "]);
+    }
+
+    #[test]
+    fn should_style_lines_with_styler() {
+        let widget = widget(&["let x", "é let y"]);
+        let styler: LineStyler = Box::new(|row, line| match row {
+            0 => Vec::new(),
+            _ => line
+                .char_indices()
+                .position(|(i, _)| line[i..].starts_with("let"))
+                .map(|start| (start..start + 3, Style::default().fg(Color::Blue)))
+                .into_iter()
+                .collect(),
+        });
+        let area = Rect::new(0, 0, 8, 2);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget).line_styler(Some(&styler)).render(
+            area,
+            &mut buf,
+            &mut Viewport::default(),
+        );
+        assert_eq!(buf[(0, 0)].fg, Color::Reset);
+        assert_eq!(buf[(1, 1)].fg, Color::Reset);
+        assert_eq!(buf[(2, 1)].fg, Color::Blue);
+        assert_eq!(buf[(4, 1)].fg, Color::Blue);
+        assert_eq!(buf[(5, 1)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn should_render_highlights() {
+        let widget = widget(&["(a)"]);
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        let style = Style::default().fg(Color::Red);
+        Renderer::new(&widget)
+            .highlights(vec![((0, 0), style), ((0, 2), style)])
+            .render(area, &mut buf, &mut Viewport::default());
+        assert_eq!(buf[(0, 0)].fg, Color::Red);
+        assert_eq!(buf[(0, 0)].modifier, TextModifiers::REVERSED);
+        assert_eq!(buf[(1, 0)].fg, Color::Reset);
+        assert_eq!(buf[(2, 0)].fg, Color::Red);
+    }
+
+    #[test]
+    fn should_render_line_highlights() {
+        let mut widget = widget(&["ab", "cd", "ef"]);
+        widget.set_cursor_line_style(Style::default().bg(Color::Blue));
+        widget.move_cursor(CursorMove::Down);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 3));
+        let highlights = HashMap::from([
+            (0, Style::default().bg(Color::Green)),
+            (1, Style::default().bg(Color::Red)),
+            (9, Style::default().bg(Color::Red)),
+        ]);
+        Renderer::new(&widget)
+            .line_highlights(Some(&highlights))
+            .render(buf.area, &mut buf, &mut Viewport::default());
+        // the whole row is highlighted
+        assert_eq!(buf[(0, 0)].bg, Color::Green);
+        assert_eq!(buf[(3, 0)].bg, Color::Green);
+        // the cursor line style wins over the line highlight
+        assert_eq!(buf[(1, 1)].bg, Color::Blue);
+        assert_eq!(buf[(3, 1)].bg, Color::Red);
+        assert_eq!(buf[(0, 2)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn should_render_placeholder_while_empty() {
+        let mut widget = widget(&[]);
+        widget.set_placeholder_text("Type here");
+        widget.set_placeholder_style(Style::default().fg(Color::DarkGray));
+        let buf = render(&widget, 12, 1, &mut Viewport::default());
+        assert_eq!(text(&buf), vec![" Type here  "]);
+        assert_eq!(buf[(1, 0)].fg, Color::DarkGray);
+        widget.insert_char('a');
+        let buf = render(&widget, 12, 1, &mut Viewport::default());
+        assert_eq!(text(&buf), vec!["a           "]);
+    }
+
+    #[test]
+    fn should_wrap_long_lines() {
+        let mut widget = widget(&["hello world", "abc"]);
+        widget.set_line_number_style(Style::default());
+        let buf = render_wrapped(&widget, 8, 4, &mut Viewport::default());
+        assert_eq!(
+            text(&buf),
+            vec![" 1 hello", "    worl", "   d    ", " 2 abc  "]
+        );
+    }
+
+    #[test]
+    fn should_indent_wrapped_rows() {
+        let widget = widget(&["  abcdefghij", "x"]);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 4));
+        let renderer = || Renderer::new(&widget).line_wrap(true).wrap_indent(true);
+        renderer().render(buf.area, &mut buf, &mut Viewport::default());
+        assert_eq!(text(&buf), vec!["  abcd", "  efgh", "  ij  ", "x     "]);
+        assert_eq!(
+            renderer().position_at(buf.area, &Viewport::default(), (3, 1)),
+            Some((0, 7))
+        );
+        assert_eq!(
+            renderer().position_at(buf.area, &Viewport::default(), (0, 2)),
+            Some((0, 10))
+        );
+    }
+
+    #[test]
+    fn should_count_text_rows() {
+        let mut widget = widget(&["hello world", "", "foo"]);
+        assert_eq!(Renderer::new(&widget).text_rows(6), 3);
+        assert_eq!(Renderer::new(&widget).line_wrap(true).text_rows(6), 4);
+        widget.set_line_number_style(Style::default());
+        assert_eq!(Renderer::new(&widget).line_wrap(true).text_rows(9), 4);
+    }
+
+    #[test]
+    fn should_scroll_wrapped_viewport_to_cursor() {
+        let mut widget = widget(&["0", "abcdefgh", "2"]);
+        widget.move_cursor(CursorMove::Down);
+        widget.move_cursor(CursorMove::End);
+        let mut viewport = Viewport::default();
+        let buf = render_wrapped(&widget, 4, 3, &mut viewport);
+        // the cursor at the end of a full row is drawn on the next row
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 1,
+                col: 0,
+                skip: 0
+            }
+        );
+        assert_eq!(text(&buf), vec!["abcd", "efgh", "    "]);
+        widget.move_cursor(CursorMove::Bottom);
+        let buf = render_wrapped(&widget, 4, 3, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 1,
+                col: 0,
+                skip: 0
+            }
+        );
+        assert_eq!(text(&buf), vec!["abcd", "efgh", "2   "]);
+        widget.move_cursor(CursorMove::Top);
+        render_wrapped(&widget, 4, 3, &mut viewport);
+        assert_eq!(
+            viewport,
+            Viewport {
+                row: 0,
+                col: 0,
+                skip: 0
+            }
+        );
+    }
+
+    #[test]
+    fn should_scroll_within_line_taller_than_area() {
+        let mut widget = widget(&["abcdefghijklmn", "x"]);
+        let area = Rect::new(0, 0, 4, 3);
+        let mut viewport = Viewport::default();
+        let render = |widget: &TextAreaWidget, viewport: &mut Viewport| {
+            let mut buf = Buffer::empty(area);
+            Renderer::new(widget)
+                .line_wrap(true)
+                .scroll_off(1)
+                .render(area, &mut buf, viewport);
+            text(&buf)
+        };
+        widget.move_cursor(CursorMove::Jump(0, 9));
+        assert_eq!(render(&widget, &mut viewport), vec!["efgh", "ijkl", "mn  "]);
+        assert_eq!(viewport.skip, 1);
+        // the context below the cursor is kept, past the end of the line
+        widget.move_cursor(CursorMove::End);
+        assert_eq!(render(&widget, &mut viewport), vec!["ijkl", "mn  ", "x   "]);
+        assert_eq!(viewport.skip, 2);
+        widget.move_cursor(CursorMove::Jump(0, 5));
+        assert_eq!(render(&widget, &mut viewport), vec!["abcd", "efgh", "ijkl"]);
+        assert_eq!(viewport.skip, 0);
+        // the skipped rows are dropped once the line is scrolled out of the top
+        widget.move_cursor(CursorMove::Bottom);
+        assert_eq!(render(&widget, &mut viewport), vec!["x   ", "    ", "    "]);
+        assert_eq!((viewport.row, viewport.skip), (1, 0));
+    }
+
+    #[test]
+    fn should_render_whitespace_glyphs() {
+        let mut widget = widget(&["  a b \tc ", "\t"]);
+        widget.set_tab_length(4);
+        widget.move_cursor(CursorMove::Down);
+        let area = Rect::new(0, 0, 12, 2);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget).whitespace(Some(('·', '→'))).render(
+            area,
+            &mut buf,
+            &mut Viewport::default(),
+        );
+        assert_eq!(text(&buf), vec!["··a b → c·  ", "→           "]);
+        assert_eq!(buf[(0, 0)].modifier, TextModifiers::DIM);
+        assert_eq!(buf[(2, 0)].modifier, TextModifiers::empty());
+        assert_eq!(buf[(3, 0)].modifier, TextModifiers::empty());
+    }
+
+    #[test]
+    fn should_render_tab_char() {
+        let mut widget = widget(&["\ta\tb", " c"]);
+        widget.set_tab_length(4);
+        widget.move_cursor(CursorMove::Down);
+        let area = Rect::new(0, 0, 10, 2);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget)
+            .whitespace(Some(('·', '→')))
+            .tab_char(Some('▸'))
+            .render(area, &mut buf, &mut Viewport::default());
+        assert_eq!(text(&buf), vec!["▸   a▸  b ", "·c        "]);
+        assert_eq!(buf[(0, 0)].modifier, TextModifiers::DIM);
+        assert_eq!(buf[(4, 0)].modifier, TextModifiers::empty());
+    }
+
+    #[test]
+    fn should_render_rulers() {
+        let mut widget = widget(&["hello world", "hi"]);
+        widget.set_line_number_style(Style::default());
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buf = Buffer::empty(area);
+        let mut viewport = Viewport::default();
+        let style = Style::default().fg(Color::DarkGray);
+        Renderer::new(&widget)
+            .rulers(vec![2, 6, 20], style)
+            .render(area, &mut buf, &mut viewport);
+        assert_eq!(
+            text(&buf),
+            vec![" 1 hello wor", " 2 hi│   │  ", "     │   │  "]
+        );
+        assert_eq!(buf[(5, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(4, 0)].fg, Color::Reset);
+        // rulers scroll along with the text
+        viewport.col = 2;
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget)
+            .follow_cursor(false)
+            .rulers(vec![2, 6], style)
+            .render(area, &mut buf, &mut viewport);
+        assert_eq!(
+            text(&buf),
+            vec![" 1 llo world", " 2 │   │    ", "   │   │    "]
+        );
+    }
+
+    #[test]
+    fn should_render_indent_guides() {
+        let mut widget = widget(&["a", "    b", "  \t  c", "     "]);
+        widget.set_tab_length(2);
+        let area = Rect::new(0, 0, 8, 4);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(&widget)
+            .indent_guides(Some(Style::default().fg(Color::DarkGray)))
+            .render(area, &mut buf, &mut Viewport::default());
+        assert_eq!(
+            text(&buf),
+            vec!["a       ", "│ │ b   ", "│ │ │ c ", "│ │ │   "]
+        );
+        assert_eq!(buf[(2, 1)].fg, Color::DarkGray);
+        assert_eq!(buf[(3, 1)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn should_get_position_at_cell() {
+        let mut widget = widget(&["hello", "\tworld", "abcdefgh"]);
+        widget.set_line_number_style(Style::default());
+        widget.set_tab_length(4);
+        let area = Rect::new(2, 1, 10, 3);
+        let viewport = Viewport {
+            row: 0,
+            col: 1,
+            skip: 0,
+        };
+        let renderer = Renderer::new(&widget);
+        // gutter and outside of the area
+        assert_eq!(renderer.position_at(area, &viewport, (4, 1)), None);
+        assert_eq!(renderer.position_at(area, &viewport, (12, 1)), None);
+        assert_eq!(renderer.position_at(area, &viewport, (6, 4)), None);
+        assert_eq!(renderer.position_at(area, &viewport, (5, 1)), Some((0, 1)));
+        assert_eq!(renderer.position_at(area, &viewport, (7, 2)), Some((1, 0)));
+        assert_eq!(renderer.position_at(area, &viewport, (8, 2)), Some((1, 1)));
+        assert_eq!(renderer.position_at(area, &viewport, (11, 1)), Some((0, 5)));
+        // wrapped lines
+        let renderer = Renderer::new(&widget).line_wrap(true);
+        let viewport = Viewport {
+            row: 2,
+            col: 0,
+            skip: 0,
+        };
+        assert_eq!(renderer.position_at(area, &viewport, (11, 1)), Some((2, 6)));
+        assert_eq!(renderer.position_at(area, &viewport, (6, 2)), Some((2, 8)));
+        assert_eq!(renderer.position_at(area, &viewport, (6, 3)), None);
+    }
+
+    fn widget<'a>(lines: &[&str]) -> TextAreaWidget<'a> {
+        TextAreaWidget::new(lines.iter().map(|x| x.to_string()).collect())
+    }
+
+    fn render(widget: &TextAreaWidget, width: u16, height: u16, viewport: &mut Viewport) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(widget).render(area, &mut buf, viewport);
+        buf
+    }
+
+    fn render_wrapped(
+        widget: &TextAreaWidget,
+        width: u16,
+        height: u16,
+        viewport: &mut Viewport,
+    ) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        Renderer::new(widget)
+            .line_wrap(true)
+            .render(area, &mut buf, viewport);
+        buf
+    }
+
+    fn text(buf: &Buffer) -> Vec<String> {
+        (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,104 @@
+//! # search
+//!
+//! Module which provides the search options, used to build the pattern given to the textarea widget
+
+use lazy_regex::{regex, Regex};
+
+/// Options which define how the search pattern matches the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Treat the pattern as a regex; otherwise the pattern is matched literally
+    pub regex: bool,
+    /// Match the pattern ignoring case
+    pub case_insensitive: bool,
+    /// Match the pattern only as a whole word
+    pub whole_word: bool,
+    /// Wrap around the start and the end of the text when moving to the next match
+    pub wrap: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            regex: true,
+            case_insensitive: false,
+            whole_word: false,
+            wrap: true,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Make the regex which matches `pattern` with these options.
+    /// An empty pattern stays empty, so that the search is cleared
+    pub fn pattern(&self, pattern: &str) -> String {
+        if pattern.is_empty() {
+            return String::new();
+        }
+        let mut pattern = match self.regex {
+            true => pattern.to_string(),
+            false => regex::escape(pattern),
+        };
+        if self.whole_word {
+            pattern = format!(r"\b(?:{pattern})\b");
+        }
+        if self.case_insensitive {
+            pattern = format!("(?i){pattern}");
+        }
+        pattern
+    }
+}
+
+/// Count the matches of `pattern` in `lines`, returning the amount of matches starting before or
+/// at `cursor` and the total amount of matches
+pub fn count_matches(pattern: &Regex, lines: &[String], cursor: (usize, usize)) -> (usize, usize) {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            pattern
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(move |m| (row, line[..m.start()].chars().count()))
+        })
+        .fold((0, 0), |(current, total), pos| match pos <= cursor {
+            true => (current + 1, total + 1),
+            false => (current, total + 1),
+        })
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_make_pattern() {
+        let options = SearchOptions::default();
+        assert_eq!(options.pattern("a.b"), "a.b");
+        assert_eq!(options.pattern(""), "");
+        let options = SearchOptions {
+            regex: false,
+            case_insensitive: true,
+            whole_word: true,
+            wrap: true,
+        };
+        assert_eq!(options.pattern("a.b"), r"(?i)\b(?:a\.b)\b");
+    }
+
+    #[test]
+    fn should_count_matches() {
+        let pattern = Regex::new("o+").unwrap();
+        let lines = vec![
+            String::from("foo boo"),
+            String::from("x"),
+            String::from("zoo"),
+        ];
+        assert_eq!(count_matches(&pattern, &lines, (0, 0)), (0, 3));
+        assert_eq!(count_matches(&pattern, &lines, (0, 5)), (2, 3));
+        assert_eq!(count_matches(&pattern, &lines, (2, 3)), (3, 3));
+        assert_eq!(count_matches(&pattern, &lines[1..2], (0, 0)), (0, 0));
+    }
+}
@@ -0,0 +1,17 @@
+//! # snapshot
+//!
+//! Module which provides the snapshot of the editor content, used to persist an editing session
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The content of the textarea and the position of its cursor, taken with `TextArea::snapshot`
+/// and put back with `TextArea::restore`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextAreaSnapshot {
+    /// Lines of the text
+    pub lines: Vec<String>,
+    /// Cursor position as `(row, column)`
+    pub cursor: (usize, usize),
+}
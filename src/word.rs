@@ -0,0 +1,74 @@
+//! # word
+//!
+//! Module which provides the helpers to find the word at the cursor and its occurrences
+
+/// Whether `ch` is part of a word
+pub fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Get the word at `col` in `line`, or the one ending before it at the end of the line,
+/// as its char range. Returns `None` if `col` is on whitespace or on punctuation
+pub fn word_at(line: &str, col: usize) -> Option<(usize, usize)> {
+    let chars: Vec<char> = line.chars().collect();
+    let col = match chars.get(col) {
+        Some(ch) if is_word_char(*ch) => col,
+        None if col > 0 && chars.get(col - 1).is_some_and(|ch| is_word_char(*ch)) => col - 1,
+        _ => return None,
+    };
+    let start = chars[..col]
+        .iter()
+        .rposition(|ch| !is_word_char(*ch))
+        .map_or(0, |x| x + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|ch| !is_word_char(*ch))
+        .map_or(chars.len(), |x| col + x);
+    Some((start, end))
+}
+
+/// Get the char columns where `word` occurs in `line` as a whole word
+pub fn word_occurrences(line: &str, word: &str) -> Vec<usize> {
+    let chars: Vec<char> = line.chars().collect();
+    let word: Vec<char> = word.chars().collect();
+    if word.is_empty() {
+        return Vec::new();
+    }
+    (0..chars.len().saturating_sub(word.len() - 1))
+        .filter(|col| {
+            chars[*col..].starts_with(&word)
+                && (*col == 0 || !is_word_char(chars[col - 1]))
+                && chars
+                    .get(col + word.len())
+                    .is_none_or(|ch| !is_word_char(*ch))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_get_word_at_cursor() {
+        assert_eq!(word_at("let föo_1 = x;", 5), Some((4, 9)));
+        assert_eq!(word_at("let föo_1 = x;", 4), Some((4, 9)));
+        assert_eq!(word_at("let föo_1 = x;", 9), None);
+        assert_eq!(word_at("let föo_1 = x;", 13), None);
+        assert_eq!(word_at("let x", 5), Some((4, 5)));
+        assert_eq!(word_at("", 0), None);
+    }
+
+    #[test]
+    fn should_find_word_occurrences() {
+        assert_eq!(
+            word_occurrences("foo(foo_bar, foo) + afoo", "foo"),
+            vec![0, 13]
+        );
+        assert_eq!(word_occurrences("日本 日本", "日本"), vec![0, 3]);
+        assert_eq!(word_occurrences("x", "xy"), Vec::<usize>::new());
+    }
+}
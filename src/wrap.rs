@@ -0,0 +1,102 @@
+//! # wrap
+//!
+//! Module which provides the soft wrap algorithm, used to split a logical line into visual rows
+
+use unicode_width::UnicodeWidthChar;
+
+/// Get the display width of `ch` when it is rendered at the display column `col`.
+/// Tabs are expanded up to the next tab stop.
+pub fn char_width(ch: char, col: usize, tab_len: u8) -> usize {
+    if ch == '\t' {
+        match tab_len {
+            0 => 0,
+            len => len as usize - (col % len as usize),
+        }
+    } else {
+        ch.width().unwrap_or(0)
+    }
+}
+
+/// Get the indent of the rows continuing `line` when it is wrapped at `width` columns, which is the
+/// display width of its leading whitespace, or `0` if it doesn't leave room for the text
+pub fn break_indent(line: &str, width: usize, tab_len: u8) -> usize {
+    let indent = line
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .fold(0, |col, ch| col + char_width(ch, col, tab_len));
+    match indent < width {
+        true => indent,
+        false => 0,
+    }
+}
+
+/// Split `line` into visual rows of at most `width` columns, where the rows after the first one are
+/// `indent` columns narrower.
+/// Returns the char index of the first char of each row; the first row always starts at `0`.
+/// A char wider than the row is put on its own row.
+pub fn wrap_line(line: &str, width: usize, indent: usize, tab_len: u8) -> Vec<usize> {
+    let width = width.max(1);
+    let mut rows = vec![0];
+    let mut col = 0;
+    let mut row_width = 0;
+    for (i, ch) in line.chars().enumerate() {
+        let w = char_width(ch, col, tab_len);
+        let max = match rows.len() {
+            1 => width,
+            _ => width.saturating_sub(indent).max(1),
+        };
+        if row_width > 0 && row_width + w > max {
+            rows.push(i);
+            row_width = 0;
+        }
+        row_width += w;
+        col += w;
+    }
+    rows
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_get_char_width() {
+        assert_eq!(char_width('a', 0, 4), 1);
+        assert_eq!(char_width('漢', 0, 4), 2);
+        assert_eq!(char_width('\t', 0, 4), 4);
+        assert_eq!(char_width('\t', 5, 4), 3);
+        assert_eq!(char_width('\t', 5, 0), 0);
+    }
+
+    #[test]
+    fn should_wrap_line() {
+        assert_eq!(wrap_line("", 4, 0, 4), vec![0]);
+        assert_eq!(wrap_line("abcd", 4, 0, 4), vec![0]);
+        assert_eq!(wrap_line("abcdefghij", 4, 0, 4), vec![0, 4, 8]);
+        assert_eq!(wrap_line("a漢字b", 4, 0, 4), vec![0, 2]);
+        assert_eq!(wrap_line("ab\tc", 4, 0, 4), vec![0, 3]);
+    }
+
+    #[test]
+    fn should_get_break_indent() {
+        assert_eq!(break_indent("  foo", 8, 4), 2);
+        assert_eq!(break_indent(" \tfoo", 8, 4), 4);
+        assert_eq!(break_indent("foo", 8, 4), 0);
+        assert_eq!(break_indent("        foo", 8, 4), 0);
+    }
+
+    #[test]
+    fn should_wrap_line_with_indent() {
+        assert_eq!(wrap_line("  abcdefghij", 6, 2, 4), vec![0, 6, 10]);
+        assert_eq!(wrap_line("  abcdefghij", 6, 0, 4), vec![0, 6]);
+    }
+
+    #[test]
+    fn should_wrap_line_narrower_than_char() {
+        assert_eq!(wrap_line("漢字", 1, 0, 4), vec![0, 1]);
+        assert_eq!(wrap_line("abc", 0, 0, 4), vec![0, 1, 2]);
+    }
+}